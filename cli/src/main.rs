@@ -0,0 +1,593 @@
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use solana_sdk::{
+  instruction::Instruction,
+  native_token::{lamports_to_sol, sol_to_lamports},
+  pubkey::{Pubkey, MAX_SEED_LEN},
+  signature::{read_keypair_file, Keypair, Signer},
+  transaction::Transaction,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use trade::{
+  client::{self, Oracle},
+  pda,
+  event::TradeEvent,
+  instruction::{ListMarket, Make, Resolution, ResolveTrade, Take},
+  state::{ContractSize, Direction, Duration, OrderStatus, Outcome, Party, Trade, User},
+};
+
+mod rpc;
+use rpc::RpcClient;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Defaults
+const LOCALHOST_URL: &str = "http://127.0.0.1:8899";
+const DEVNET_URL: &str = "https://api.devnet.solana.com";
+const DEFAULT_PROGRAM_KEYPAIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy/trade-keypair.json");
+const DEFAULT_FEED: &str = "HgTtcbcmp5BeThax5AU8vg4VwK79qAvAKKFMs8txMLW6"; // Chainlink SOL / USD (Devnet)
+const DEFAULT_SYMBOL: &str = "SOL / USD";
+const MAX_COLLECT: usize = 20; // Settled Trades collected per Withdraw (transaction size)
+const MAX_CLAIM_BATCH: usize = 20; // Expired Trades claimed per Claim Batch (transaction size)
+
+
+// Shared state for every Command
+struct Context {
+  rpc: RpcClient,
+  payer: Keypair,
+  program_id: Pubkey,
+  oracle: Oracle,
+}
+
+impl Context {
+
+  fn from_matches(matches: &ArgMatches) -> Result<Self> {
+    let url = match matches.value_of("url").unwrap() {
+      "localhost" | "l" => LOCALHOST_URL,
+      "devnet" | "d" => DEVNET_URL,
+      url => url,
+    };
+    let keypair_path = match matches.value_of("keypair") {
+      Some(path) => String::from(path),
+      None => format!("{}/.config/solana/id.json", std::env::var("HOME")?),
+    };
+    let payer = read_keypair_file(&keypair_path).map_err(|e| format!("Keypair {}: {}", keypair_path, e))?;
+    let oracle = Oracle::chainlink(Pubkey::from_str(matches.value_of("feed").unwrap())?); // Program pins Chainlink
+    Ok(Self {
+      rpc: RpcClient::new(String::from(url)),
+      payer,
+      program_id: pubkey_or_keypair(matches.value_of("program_id").unwrap())?,
+      oracle,
+    })
+  }
+
+  // Sign with the Keypair, send and print the Program Events
+  fn send(&self, instruction: Instruction) -> Result<()> {
+    let blockhash = self.rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+      &[instruction],
+      Some(&self.payer.pubkey()),
+      &[&self.payer],
+      blockhash,
+    );
+    let (signature, logs) = self.rpc.send_and_confirm(&transaction)?;
+    println!("Signature: {}", signature);
+    for event in TradeEvent::decode_logs(&logs) {
+      println!("{:?}", event);
+    }
+    Ok(())
+  }
+
+  fn fetch_trade(&self, address: &Pubkey) -> Result<Trade> {
+    let data = self.rpc.get_account_data(address)?.ok_or(format!("Trade Account {} not found", address))?;
+    Ok(client::decode_trade(&data)?)
+  }
+
+  fn fetch_user(&self, address: &Pubkey) -> Result<User> {
+    let data = self.rpc.get_account_data(address)?.ok_or(format!("User Account {} not found", address))?;
+    Ok(client::decode_user(&data)?)
+  }
+
+  // Settled Trades with a Payout of the User still to collect (oldest first)
+  fn fetch_uncollected(&self, user: &Pubkey) -> Result<Vec<Pubkey>> {
+    let mut trades = Vec::new();
+    for (address, data) in self.rpc.get_program_accounts(&self.program_id, Trade::LEN)? {
+      let trade = client::decode_trade(&data)?;
+      if !trade.uncollected(user).is_empty() {
+        trades.push((trade.unix_claimed, address));
+      }
+    }
+    trades.sort();
+    Ok(trades.into_iter().map(|(_, address)| address).take(MAX_COLLECT).collect())
+  }
+}
+
+
+// Run CLI
+fn main() {
+  let matches = app().get_matches();
+  let result = Context::from_matches(&matches).and_then(|ctx| match matches.subcommand() {
+    ("user", Some(sub)) => process_user(&ctx, sub),
+    ("trade", Some(sub)) => process_trade(&ctx, sub),
+    ("admin", Some(sub)) => process_admin(&ctx, sub),
+    _ => unreachable!(),
+  });
+  if let Err(error) = result {
+    eprintln!("Error: {}", error);
+    std::process::exit(1);
+  }
+}
+
+fn app() -> App<'static, 'static> {
+  let trade_arg = Arg::with_name("trade").value_name("TRADE").required(true).help("Trade Account address");
+  let symbol_arg = Arg::with_name("symbol").value_name("SYMBOL").required(true).help("Market Symbol")
+    .validator(seed_validator);
+
+  App::new("trade-cli")
+    .about("Command-line client for the Trade Program")
+    .setting(AppSettings::SubcommandRequiredElseHelp)
+    .arg(Arg::with_name("url").long("url").short("u").global(true).takes_value(true).default_value("localhost")
+      .help("RPC URL or moniker (localhost, devnet)"))
+    .arg(Arg::with_name("keypair").long("keypair").short("k").global(true).takes_value(true)
+      .help("Signer Keypair file [default: ~/.config/solana/id.json]"))
+    .arg(Arg::with_name("program_id").long("program-id").global(true).takes_value(true)
+      .default_value(DEFAULT_PROGRAM_KEYPAIR).help("Program address or Program Keypair file"))
+    .arg(Arg::with_name("feed").long("feed").global(true).takes_value(true).default_value(DEFAULT_FEED)
+      .help("Price Feed Account"))
+    .subcommand(SubCommand::with_name("user").about("Manage the User Account")
+      .setting(AppSettings::SubcommandRequiredElseHelp)
+      .subcommand(SubCommand::with_name("create").about("Create the User Account"))
+      .subcommand(SubCommand::with_name("deposit").about("Deposit SOL into the User Account")
+        .arg(Arg::with_name("amount").value_name("SOL").required(true)))
+      .subcommand(SubCommand::with_name("withdraw").about("Collect settled Trades and withdraw SOL from the Free Balance")
+        .arg(Arg::with_name("amount").value_name("SOL").help("Omit to withdraw the full Free Balance")))
+      .subcommand(SubCommand::with_name("show").about("Show a User Account")
+        .arg(Arg::with_name("authority").value_name("AUTHORITY").help("Defaults to the Keypair"))))
+    .subcommand(SubCommand::with_name("trade").about("Make, Take, Cancel and Claim Trades")
+      .setting(AppSettings::SubcommandRequiredElseHelp)
+      .subcommand(SubCommand::with_name("make").about("Open a Trade")
+        .arg(Arg::with_name("slug").value_name("SLUG").required(true).help("Unique per Maker (used in the Trade PDA)")
+          .validator(seed_validator))
+        .arg(Arg::with_name("symbol").long("symbol").takes_value(true).default_value(DEFAULT_SYMBOL))
+        .arg(Arg::with_name("size").long("size").takes_value(true).default_value("1")
+          .possible_values(&["0.1", "1", "5"]).help("Contract Size in SOL"))
+        .arg(Arg::with_name("direction").long("direction").takes_value(true).default_value("long")
+          .possible_values(&["long", "short"]))
+        .arg(Arg::with_name("duration").long("duration").takes_value(true).default_value("5m")
+          .help("5m, 1h, 1d or a custom length (e.g. 90s, 15m, 4h)"))
+        .arg(Arg::with_name("expiry").long("expiry").value_name("UNIX").takes_value(true).conflicts_with("duration")
+          .help("Settle at this calendar Expiry of the Market instead"))
+        .arg(Arg::with_name("taker").long("taker").value_name("AUTHORITY").takes_value(true)
+          .help("Only this Taker Authority may Take (OTC)"))
+        .arg(Arg::with_name("reference-price").long("reference-price").value_name("PRICE").takes_value(true)
+          .help("Fill only near this price (Chainlink units)"))
+        .arg(Arg::with_name("max-deviation-bps").long("max-deviation-bps").value_name("BPS").takes_value(true)
+          .default_value("0").requires("reference-price")))
+      .subcommand(SubCommand::with_name("take").about("Take an Open Trade")
+        .arg(trade_arg.clone())
+        .arg(Arg::with_name("direction").long("direction").takes_value(true).possible_values(&["long", "short"])
+          .help("Defaults to the opposite of the Maker"))
+        .arg(Arg::with_name("min-price").long("min-price").value_name("PRICE").takes_value(true)
+          .help("Lowest acceptable Benchmark Price (Chainlink units)"))
+        .arg(Arg::with_name("max-price").long("max-price").value_name("PRICE").takes_value(true)
+          .help("Highest acceptable Benchmark Price (Chainlink units)")))
+      .subcommand(SubCommand::with_name("cancel").about("Cancel an unfilled Open Trade").arg(trade_arg.clone()))
+      .subcommand(SubCommand::with_name("claim").about("Settle an InPlay Trade").arg(trade_arg.clone()))
+      .subcommand(SubCommand::with_name("claim-expired").about("Settle every expired Trade of a Symbol in batches")
+        .arg(Arg::with_name("symbol").long("symbol").takes_value(true).default_value(DEFAULT_SYMBOL))
+        .arg(Arg::with_name("round").long("round").takes_value(true)
+          .help("Chainlink round to settle on, live or observed (default: the first observed round after each end)")))
+      .subcommand(SubCommand::with_name("observe").about("Record the latest price for TWAP settlement (any Keypair)")
+        .arg(Arg::with_name("symbol").long("symbol").takes_value(true).default_value(DEFAULT_SYMBOL)))
+      .subcommand(SubCommand::with_name("history").about("Print observed prices oldest first (timestamp, round, price)")
+        .arg(Arg::with_name("symbol").long("symbol").takes_value(true).default_value(DEFAULT_SYMBOL)))
+      .subcommand(SubCommand::with_name("show").about("Show a Trade").arg(trade_arg))
+      .subcommand(SubCommand::with_name("list").about("List Trades")
+        .arg(Arg::with_name("status").long("status").takes_value(true)
+          .possible_values(&["open", "inplay", "settled", "cancelled", "expired", "pending", "voided"]))
+        .arg(Arg::with_name("mine").long("mine").help("Only Trades made or taken by the Keypair"))))
+    .subcommand(SubCommand::with_name("admin").about("Admin Instructions")
+      .setting(AppSettings::SubcommandRequiredElseHelp)
+      .subcommand(SubCommand::with_name("init").about("Create Config (the Keypair must be the Program's Upgrade Authority and becomes Admin)"))
+      .subcommand(SubCommand::with_name("list-market").about("List a Market").arg(symbol_arg.clone())
+        .arg(Arg::with_name("min-duration").long("min-duration").takes_value(true).default_value("1m"))
+        .arg(Arg::with_name("max-duration").long("max-duration").takes_value(true).default_value("1d"))
+        .arg(Arg::with_name("expiry-interval").long("expiry-interval").takes_value(true).default_value("0")
+          .help("Calendar Expiries every interval (e.g. 1h, 1d), 0 for none"))
+        .arg(Arg::with_name("expiry-offset").long("expiry-offset").takes_value(true).default_value("0")
+          .help("Calendar Expiries at offset past each interval (e.g. 16h with a 1d interval)"))
+        .arg(Arg::with_name("tie").long("tie").takes_value(true).default_value("void")
+          .possible_values(&["void", "maker", "taker"]).help("Outcome when the Closing Price equals the Benchmark"))
+        .arg(Arg::with_name("late").long("late").takes_value(true).default_value("void")
+          .possible_values(&["void", "maker", "taker"]).help("Outcome when Claimed after the claim window"))
+        .arg(Arg::with_name("claim-window").long("claim-window").takes_value(true).default_value("1d")
+          .help("Time after the end before a Claim is late (0 = never)"))
+        .arg(Arg::with_name("challenge-window").long("challenge-window").takes_value(true).default_value("0")
+          .help("Time after a Claim during which the Resolver may void or correct it (0 = none)"))
+        .arg(Arg::with_name("twap-window").long("twap-window").takes_value(true).default_value("0")
+          .help("Settle on the average observed price over this time before the end (0 = latest round)"))
+        .arg(Arg::with_name("max-observation-age").long("max-observation-age").takes_value(true).default_value("0")
+          .help("Claim on an observed price at most this old instead of a live round (0 = live only)"))
+        .arg(Arg::with_name("dust").long("dust").takes_value(true).default_value("maker")
+          .possible_values(&["maker", "taker"]).help("Receives lamports above the two Stakes")))
+      .subcommand(SubCommand::with_name("pause").about("Set Pause Flags")
+        .arg(Arg::with_name("flags").value_name("FLAGS").required(true)
+          .help("1 = Make, 2 = Take, 4 = Claim, 8 = Withdraw (0 to resume)")))
+      .subcommand(SubCommand::with_name("halt").about("Halt a Market").arg(symbol_arg.clone()))
+      .subcommand(SubCommand::with_name("resume").about("Resume a halted Market").arg(symbol_arg))
+      .subcommand(SubCommand::with_name("set-resolver").about("Set the Resolver of disputed Trades")
+        .arg(Arg::with_name("resolver").value_name("PUBKEY").required(true)))
+      .subcommand(SubCommand::with_name("resolve").about("Void a Trade or correct its Closing Price")
+        .arg(Arg::with_name("trade").value_name("TRADE").required(true))
+        .arg(Arg::with_name("void").long("void").conflicts_with("price").required_unless("price"))
+        .arg(Arg::with_name("price").long("price").takes_value(true).value_name("PRICE"))
+        .arg(Arg::with_name("reason").long("reason").takes_value(true).required(true)))
+      .subcommand(SubCommand::with_name("propose").about("Propose a new Admin")
+        .arg(Arg::with_name("new_admin").value_name("PUBKEY").required(true)))
+      .subcommand(SubCommand::with_name("accept").about("Accept the Admin role as Pending Admin")))
+}
+
+
+// USER COMMANDS
+fn process_user(ctx: &Context, matches: &ArgMatches) -> Result<()> {
+  let authority = ctx.payer.pubkey();
+  match matches.subcommand() {
+    ("create", _) => ctx.send(client::create_user(&ctx.program_id, &authority)),
+    ("deposit", Some(sub)) => {
+      let amount = sol_to_lamports(f64::from_str(sub.value_of("amount").unwrap())?);
+      ctx.send(client::deposit(&ctx.program_id, &authority, amount))
+    },
+    ("withdraw", Some(sub)) => {
+      let trades = ctx.fetch_uncollected(&pda::find_user_address(&ctx.program_id, &authority).0)?;
+      match sub.value_of("amount") {
+        Some(amount) => {
+          ctx.send(client::withdraw(&ctx.program_id, &authority, sol_to_lamports(f64::from_str(amount)?), &trades))
+        },
+        None => ctx.send(client::withdraw_balance(&ctx.program_id, &authority, &trades)),
+      }
+    },
+    ("show", Some(sub)) => {
+      let authority = match sub.value_of("authority") {
+        Some(authority) => Pubkey::from_str(authority)?,
+        None => authority,
+      };
+      let address = pda::find_user_address(&ctx.program_id, &authority).0;
+      print_user(&address, &ctx.fetch_user(&address)?);
+      Ok(())
+    },
+    _ => unreachable!(),
+  }
+}
+
+
+// TRADE COMMANDS
+fn process_trade(ctx: &Context, matches: &ArgMatches) -> Result<()> {
+  let authority = ctx.payer.pubkey();
+  match matches.subcommand() {
+    ("make", Some(sub)) => {
+      let make = Make {
+        symbol: String::from(sub.value_of("symbol").unwrap()),
+        slug: String::from(sub.value_of("slug").unwrap()),
+        contract_size: match sub.value_of("size").unwrap() {
+          "0.1" => ContractSize::Small,
+          "1" => ContractSize::Medium,
+          _ => ContractSize::Large,
+        },
+        direction: parse_direction(sub.value_of("direction").unwrap()),
+        duration: match sub.value_of("expiry") {
+          Some(expiry) => Duration::Expiry(u32::from_str(expiry)?),
+          None => parse_duration(sub.value_of("duration").unwrap())?,
+        },
+        allowed_taker: sub.value_of("taker").map(Pubkey::from_str).transpose()?.unwrap_or_default(),
+        reference_price: sub.value_of("reference-price").map(str::parse).transpose()?.unwrap_or(0),
+        max_deviation_bps: sub.value_of("max-deviation-bps").unwrap().parse()?,
+      };
+      println!("Trade: {}", pda::find_trade_address(&ctx.program_id, &make.slug, &authority).0);
+      ctx.send(client::make_trade(&ctx.program_id, &authority, &authority, &ctx.oracle, make))
+    },
+    ("take", Some(sub)) => {
+      let address = Pubkey::from_str(sub.value_of("trade").unwrap())?;
+      let trade = ctx.fetch_trade(&address)?;
+      let direction = match sub.value_of("direction") {
+        Some(direction) => parse_direction(direction),
+        None => trade.direction.opposite(),
+      };
+      let take = Take {
+        direction,
+        min_price: sub.value_of("min-price").map(str::parse).transpose()?.unwrap_or(0),
+        max_price: sub.value_of("max-price").map(str::parse).transpose()?.unwrap_or(0),
+      };
+      ctx.send(client::take_trade(&ctx.program_id, &authority, &authority, &address, &trade.symbol, &ctx.oracle, take))
+    },
+    ("cancel", Some(sub)) => {
+      let address = Pubkey::from_str(sub.value_of("trade").unwrap())?;
+      ctx.send(client::cancel_trade(&ctx.program_id, &authority, &authority, &address))
+    },
+    ("claim", Some(sub)) => {
+      let address = Pubkey::from_str(sub.value_of("trade").unwrap())?;
+      let trade = ctx.fetch_trade(&address)?;
+      ctx.send(client::claim(&ctx.program_id, &authority, &address, &trade, &ctx.oracle))
+    },
+    ("claim-expired", Some(sub)) => {
+      let symbol = sub.value_of("symbol").unwrap();
+      let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+      let mut trades = Vec::new();
+      for (address, data) in ctx.rpc.get_program_accounts(&ctx.program_id, Trade::LEN)? {
+        let trade = client::decode_trade(&data)?;
+        let claimable_from = match trade.order_status {
+          OrderStatus::InPlay => trade.unix_end,
+          OrderStatus::Pending => trade.unix_claimed.saturating_add(trade.challenge_window),
+          _ => continue,
+        };
+        if trade.symbol == symbol && u64::from(claimable_from) <= now {
+          trades.push((address, trade));
+        }
+      }
+      if trades.is_empty() {
+        println!("No expired Trades for {}", symbol);
+        return Ok(());
+      }
+
+      // Each InPlay Trade settles on the first round after its end (Pending ones on any round)
+      let address = pda::find_observations_address(&ctx.program_id, symbol).0;
+      let data = ctx.rpc.get_account_data(&address)?.ok_or(format!("Observations Account {} not found", address))?;
+      let history = client::decode_observations(&data)?.history();
+      let round: Option<u32> = sub.value_of("round").map(str::parse).transpose()?;
+      let mut batches: BTreeMap<u32, Vec<Pubkey>> = BTreeMap::new();
+      for (address, trade) in trades {
+        if trade.order_status == OrderStatus::InPlay && (trade.twap_window != 0 || trade.max_observation_age != 0) {
+          ctx.send(client::claim(&ctx.program_id, &authority, &address, &trade, &ctx.oracle))?; // Priced by Observations
+          continue;
+        }
+        let observed = match trade.order_status {
+          OrderStatus::InPlay => history.iter().find(|sample| sample.unix_timestamp >= trade.unix_end),
+          _ => history.last(),
+        };
+        match round.or(observed.map(|sample| sample.round_id)) {
+          Some(round_id) => batches.entry(round_id).or_default().push(address),
+          None => println!("No observed round after the end of {}: run trade observe or pass --round", address),
+        }
+      }
+      for (round_id, trades) in batches {
+        println!("Settling {} Trades on round {}", trades.len(), round_id);
+        for batch in trades.chunks(MAX_CLAIM_BATCH) {
+          ctx.send(client::claim_batch(&ctx.program_id, &authority, symbol, batch, &ctx.oracle, round_id))?;
+        }
+      }
+      Ok(())
+    },
+    ("observe", Some(sub)) => {
+      ctx.send(client::record_observation(&ctx.program_id, sub.value_of("symbol").unwrap(), &ctx.oracle))
+    },
+    ("history", Some(sub)) => {
+      let symbol = sub.value_of("symbol").unwrap();
+      let address = pda::find_observations_address(&ctx.program_id, symbol).0;
+      let data = ctx.rpc.get_account_data(&address)?.ok_or(format!("Observations Account {} not found", address))?;
+      for observation in client::decode_observations(&data)?.history() {
+        println!("{} {} {}", observation.unix_timestamp, observation.round_id, observation.price);
+      }
+      Ok(())
+    },
+    ("show", Some(sub)) => {
+      let address = Pubkey::from_str(sub.value_of("trade").unwrap())?;
+      print_trade(&address, &ctx.fetch_trade(&address)?);
+      Ok(())
+    },
+    ("list", Some(sub)) => {
+      let status = sub.value_of("status").map(|status| match status {
+        "open" => OrderStatus::Open,
+        "inplay" => OrderStatus::InPlay,
+        "settled" => OrderStatus::Settled,
+        "cancelled" => OrderStatus::Cancelled,
+        "expired" => OrderStatus::Expired,
+        "pending" => OrderStatus::Pending,
+        _ => OrderStatus::Voided,
+      });
+      let user = pda::find_user_address(&ctx.program_id, &authority).0;
+      let mut trades = Vec::new();
+      for (address, data) in ctx.rpc.get_program_accounts(&ctx.program_id, Trade::LEN)? {
+        let trade = client::decode_trade(&data)?;
+        if status.is_none_or(|status| trade.order_status == status)
+          && (!sub.is_present("mine") || trade.maker == user || trade.taker == user) {
+          trades.push((address, trade));
+        }
+      }
+      trades.sort_by_key(|(_, trade)| trade.unix_created);
+      for (address, trade) in trades {
+        println!("{:<44}  {:<12}  {:<10}  {:<9}  {:<5}  {:>7}  {}", address, trade.slug, trade.symbol,
+          format!("{:?}", trade.order_status), format!("{:?}", trade.direction), contract_label(trade.contract_size),
+          duration_label(trade.duration));
+      }
+      Ok(())
+    },
+    _ => unreachable!(),
+  }
+}
+
+
+// ADMIN COMMANDS
+fn process_admin(ctx: &Context, matches: &ArgMatches) -> Result<()> {
+  let admin = ctx.payer.pubkey();
+  match matches.subcommand() {
+    ("init", _) => ctx.send(client::initialize_config(&ctx.program_id, &admin)),
+    ("list-market", Some(sub)) => {
+      let market = ListMarket {
+        symbol: String::from(sub.value_of("symbol").unwrap()),
+        min_duration: parse_seconds(sub.value_of("min-duration").unwrap())?,
+        max_duration: parse_seconds(sub.value_of("max-duration").unwrap())?,
+        expiry_interval: parse_seconds(sub.value_of("expiry-interval").unwrap())?,
+        expiry_offset: parse_seconds(sub.value_of("expiry-offset").unwrap())?,
+        tie_outcome: parse_outcome(sub.value_of("tie").unwrap()),
+        late_outcome: parse_outcome(sub.value_of("late").unwrap()),
+        claim_window: parse_seconds(sub.value_of("claim-window").unwrap())?,
+        dust_recipient: if sub.value_of("dust") == Some("taker") { Party::Taker } else { Party::Maker },
+        challenge_window: parse_seconds(sub.value_of("challenge-window").unwrap())?,
+        twap_window: parse_seconds(sub.value_of("twap-window").unwrap())?,
+        max_observation_age: parse_seconds(sub.value_of("max-observation-age").unwrap())?,
+      };
+      ctx.send(client::list_market(&ctx.program_id, &admin, &[], &admin, market))
+    },
+    ("pause", Some(sub)) => {
+      ctx.send(client::set_pause(&ctx.program_id, &admin, &[], u8::from_str(sub.value_of("flags").unwrap())?))
+    },
+    ("halt", Some(sub)) => {
+      ctx.send(client::set_market_halt(&ctx.program_id, &admin, &[], sub.value_of("symbol").unwrap(), true))
+    },
+    ("resume", Some(sub)) => {
+      ctx.send(client::set_market_halt(&ctx.program_id, &admin, &[], sub.value_of("symbol").unwrap(), false))
+    },
+    ("set-resolver", Some(sub)) => {
+      let resolver = Pubkey::from_str(sub.value_of("resolver").unwrap())?;
+      ctx.send(client::set_resolver(&ctx.program_id, &admin, &[], &resolver))
+    },
+    ("resolve", Some(sub)) => {
+      let address = Pubkey::from_str(sub.value_of("trade").unwrap())?;
+      let resolution = match sub.value_of("price") {
+        Some(price) => Resolution::ClosingPrice(i128::from_str(price)?),
+        None => Resolution::Void,
+      };
+      let resolve = ResolveTrade { resolution, reason: String::from(sub.value_of("reason").unwrap()) };
+      ctx.send(client::resolve_trade(&ctx.program_id, &admin, &[], &address, resolve))
+    },
+    ("propose", Some(sub)) => {
+      let new_admin = Pubkey::from_str(sub.value_of("new_admin").unwrap())?;
+      ctx.send(client::propose_admin(&ctx.program_id, &admin, &[], &new_admin))
+    },
+    ("accept", _) => ctx.send(client::accept_admin(&ctx.program_id, &admin, &[])),
+    _ => unreachable!(),
+  }
+}
+
+
+// DISPLAY
+fn print_user(address: &Pubkey, user: &User) {
+  println!("User            {}", address);
+  println!("Authority       {}", user.authority);
+  println!("Deposited       {} SOL", lamports_to_sol(user.deposited));
+  println!("Free Balance    {} SOL", lamports_to_sol(user.free_balance));
+  println!("Locked Balance  {} SOL", lamports_to_sol(user.locked_balance));
+  println!("Open Positions  {}", user.open_positions);
+  println!("Trades          {} placed ({} made, {} taken)", user.trades_placed, user.trades_made, user.trades_taken);
+  println!("Results         {} won, {} lost, {} drawn", user.trades_won, user.trades_lost, user.trades_drawn);
+  println!("Total Volume    {} SOL", lamports_to_sol(user.total_volume));
+  println!("Realized PnL    {} SOL", user.realized_pnl as f64 / 1e9);
+  if user.session_key != Pubkey::default() {
+    println!("Session Key     {} (scope {:#05b}, expires {})", user.session_key, user.session_scope, user.session_expiry);
+  }
+}
+
+fn print_trade(address: &Pubkey, trade: &Trade) {
+  println!("Trade           {}", address);
+  println!("Slug            {}", trade.slug);
+  println!("Symbol          {}", trade.symbol);
+  println!("Status          {:?}", trade.order_status);
+  println!("Maker           {}", trade.maker);
+  println!("Taker           {}", if trade.taker == Pubkey::default() { String::from("-") } else { trade.taker.to_string() });
+  println!("Direction       {:?} (Maker)", trade.direction);
+  if trade.allowed_taker != Pubkey::default() {
+    println!("Allowed Taker   {}", trade.allowed_taker);
+  }
+  println!("Tie / Late      {:?} / {:?} (late after {} sec, dust to {:?})",
+    trade.tie_outcome, trade.late_outcome, trade.claim_window, trade.dust_recipient);
+  if trade.reference_price != 0 {
+    println!("Reference Price {} (within {} bps)", trade.reference_price, trade.max_deviation_bps);
+  }
+  println!("Contract        {}", contract_label(trade.contract_size));
+  println!("Duration        {}", duration_label(trade.duration));
+  if trade.twap_window != 0 {
+    println!("TWAP Window     {}s", trade.twap_window);
+  }
+  if trade.max_observation_age != 0 {
+    println!("Observed Prices up to {}s old", trade.max_observation_age);
+  }
+  println!("Created         {}", trade.unix_created);
+  if !matches!(trade.order_status, OrderStatus::Open | OrderStatus::Cancelled) {
+    println!("Start           {}", trade.unix_start);
+    println!("End             {}", trade.unix_end);
+    println!("Benchmark Price {}", trade.benchmark_price);
+  }
+  if matches!(trade.order_status, OrderStatus::Pending | OrderStatus::Settled | OrderStatus::Expired) {
+    println!("Claimed         {}", trade.unix_claimed);
+    println!("Closing Price   {}", trade.closing_price);
+  }
+  if !trade.resolution_reason.is_empty() {
+    println!("Resolved        {}", trade.resolution_reason);
+  }
+  if trade.order_status == OrderStatus::Pending {
+    println!("Final           {}", trade.unix_claimed.saturating_add(trade.challenge_window));
+  }
+  if matches!(trade.order_status, OrderStatus::Settled | OrderStatus::Expired | OrderStatus::Voided) {
+    println!("Outcome         {:?}", trade.settled_outcome);
+    println!("Maker Payout    {} SOL{}", lamports_to_sol(trade.maker_payout), if trade.maker_collected { " (collected)" } else { "" });
+    println!("Taker Payout    {} SOL{}", lamports_to_sol(trade.taker_payout), if trade.taker_collected { " (collected)" } else { "" });
+  }
+}
+
+// Slugs and Symbols are PDA seeds
+fn seed_validator(seed: String) -> std::result::Result<(), String> {
+  if seed.len() > MAX_SEED_LEN {
+    return Err(format!("at most {} bytes", MAX_SEED_LEN));
+  }
+  Ok(())
+}
+
+fn parse_direction(direction: &str) -> Direction {
+  if direction == "short" { Direction::Short } else { Direction::Long }
+}
+
+fn duration_label(duration: Duration) -> String {
+  match duration {
+    Duration::FiveMinutes => String::from("5 Min"),
+    Duration::OneHour => String::from("1 Hour"),
+    Duration::OneDay => String::from("1 Day"),
+    Duration::Seconds(seconds) => format!("{} Sec", seconds),
+    Duration::Expiry(unix_end) => format!("Exp {}", unix_end),
+  }
+}
+
+fn parse_outcome(outcome: &str) -> Outcome {
+  match outcome {
+    "maker" => Outcome::Maker,
+    "taker" => Outcome::Taker,
+    _ => Outcome::Void,
+  }
+}
+
+// Presets or a custom length
+fn parse_duration(value: &str) -> Result<Duration> {
+  Ok(match value {
+    "5m" => Duration::FiveMinutes,
+    "1h" => Duration::OneHour,
+    "1d" => Duration::OneDay,
+    _ => Duration::Seconds(parse_seconds(value)?),
+  })
+}
+
+// Seconds with an optional s, m, h or d suffix
+fn parse_seconds(value: &str) -> Result<u32> {
+  let (number, unit) = match value.char_indices().last() {
+    Some((index, 's')) => (&value[..index], 1),
+    Some((index, 'm')) => (&value[..index], 60),
+    Some((index, 'h')) => (&value[..index], 60 * 60),
+    Some((index, 'd')) => (&value[..index], 24 * 60 * 60),
+    _ => (value, 1),
+  };
+  u32::from_str(number)?.checked_mul(unit).ok_or_else(|| format!("Duration too long: {}", value).into())
+}
+
+fn contract_label(contract_size: ContractSize) -> &'static str {
+  match contract_size {
+    ContractSize::Small => "0.1 SOL",
+    ContractSize::Medium => "1 SOL",
+    ContractSize::Large => "5 SOL",
+  }
+}
+
+// Program ID from an address or a Keypair file (target/deploy/trade-keypair.json)
+fn pubkey_or_keypair(value: &str) -> Result<Pubkey> {
+  match Pubkey::from_str(value) {
+    Ok(pubkey) => Ok(pubkey),
+    Err(_) => Ok(read_keypair_file(value).map_err(|e| format!("Program Keypair {}: {}", value, e))?.pubkey()),
+  }
+}
@@ -0,0 +1,104 @@
+use serde_json::{json, Value};
+use solana_sdk::{
+  hash::Hash,
+  pubkey::Pubkey,
+  signature::Signature,
+  transaction::Transaction,
+};
+use std::{str::FromStr, thread, time::Duration};
+use crate::Result;
+
+
+// JSON RPC Client
+// Covers the handful of methods the CLI needs against any Solana RPC node (incl. solana-test-validator)
+pub struct RpcClient {
+  url: String,
+}
+
+impl RpcClient {
+
+  pub fn new(url: String) -> Self {
+    Self { url }
+  }
+
+  // Send a JSON RPC Request and return its Result
+  fn request(&self, method: &str, params: Value) -> Result<Value> {
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+    let response: Value = ureq::post(&self.url).send_json(body)?.into_json()?;
+    if let Some(error) = response.get("error") {
+      let logs = error["data"]["logs"].as_array().cloned().unwrap_or_default();
+      for log in logs {
+        eprintln!("  {}", log.as_str().unwrap_or_default());
+      }
+      return Err(format!("RPC {} failed: {}", method, error["message"]).into());
+    }
+    Ok(response["result"].clone())
+  }
+
+  // Account Data (None if the Account does not exist)
+  pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+    let result = self.request("getAccountInfo", json!([
+      address.to_string(),
+      {"encoding": "base64", "commitment": "confirmed"},
+    ]))?;
+    match result["value"]["data"][0].as_str() {
+      Some(data) => Ok(Some(base64::decode(data)?)),
+      None => Ok(None),
+    }
+  }
+
+  // Program Accounts of an exact data size
+  pub fn get_program_accounts(&self, program_id: &Pubkey, data_size: usize) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let result = self.request("getProgramAccounts", json!([
+      program_id.to_string(),
+      {"encoding": "base64", "commitment": "confirmed", "filters": [{"dataSize": data_size}]},
+    ]))?;
+    let mut accounts = Vec::new();
+    for account in result.as_array().cloned().unwrap_or_default() {
+      let address = Pubkey::from_str(account["pubkey"].as_str().unwrap_or_default())?;
+      let data = base64::decode(account["account"]["data"][0].as_str().unwrap_or_default())?;
+      accounts.push((address, data));
+    }
+    Ok(accounts)
+  }
+
+  pub fn get_latest_blockhash(&self) -> Result<Hash> {
+    let result = self.request("getLatestBlockhash", json!([{"commitment": "confirmed"}]))?;
+    Ok(Hash::from_str(result["value"]["blockhash"].as_str().unwrap_or_default())?)
+  }
+
+  // Send Transaction, wait for confirmation and return its Program Logs
+  pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<(Signature, Vec<String>)> {
+    let encoded = base64::encode(bincode::serialize(transaction)?);
+    let result = self.request("sendTransaction", json!([
+      encoded,
+      {"encoding": "base64", "preflightCommitment": "confirmed"},
+    ]))?;
+    let signature = Signature::from_str(result.as_str().unwrap_or_default())?;
+
+    // Poll Signature Status (~30 seconds)
+    for _ in 0..60 {
+      let statuses = self.request("getSignatureStatuses", json!([[signature.to_string()]]))?;
+      let status = &statuses["value"][0];
+      if !status.is_null() {
+        if !status["err"].is_null() {
+          return Err(format!("Transaction {} failed: {}", signature, status["err"]).into());
+        }
+        if matches!(status["confirmationStatus"].as_str(), Some("confirmed") | Some("finalized")) {
+          return Ok((signature, self.get_transaction_logs(&signature)?));
+        }
+      }
+      thread::sleep(Duration::from_millis(500));
+    }
+    Err(format!("Transaction {} not confirmed", signature).into())
+  }
+
+  fn get_transaction_logs(&self, signature: &Signature) -> Result<Vec<String>> {
+    let result = self.request("getTransaction", json!([
+      signature.to_string(),
+      {"encoding": "json", "commitment": "confirmed"},
+    ]))?;
+    Ok(result["meta"]["logMessages"].as_array().cloned().unwrap_or_default()
+      .iter().filter_map(|log| log.as_str().map(String::from)).collect())
+  }
+}
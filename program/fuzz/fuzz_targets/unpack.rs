@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use trade::instruction::TradeInstruction;
+
+// Arbitrary instruction bytes must decode or return an error, never panic
+// Anything accepted is canonical: packing it gives back the same bytes
+fuzz_target!(|data: &[u8]| {
+  if let Ok(instruction) = TradeInstruction::unpack(data) {
+    assert_eq!(instruction.pack(), data);
+  }
+});
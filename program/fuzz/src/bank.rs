@@ -0,0 +1,282 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use chainlink_solana::Round;
+use solana_program::{
+  account_info::AccountInfo,
+  clock::{Clock, Epoch},
+  entrypoint::ProgramResult,
+  instruction::Instruction,
+  program_error::ProgramError,
+  program_stubs::{set_syscall_stubs, SyscallStubs},
+  pubkey::Pubkey,
+  rent::Rent,
+  system_program,
+};
+use std::{
+  cell::{Cell, RefCell},
+  collections::BTreeMap,
+  sync::Once,
+};
+use trade::{event::TradeEvent, processor::Processor};
+
+
+// Oracle, Clock and pending System Program effects (fuzzing is single threaded)
+thread_local! {
+  static PROGRAM_ID: Cell<Pubkey> = Cell::new(Pubkey::default());
+  static UNIX_NOW: Cell<i64> = const { Cell::new(1_650_000_000) };
+  static FEED_PRICE: Cell<i128> = const { Cell::new(3_000_000_000) };
+  static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+  static ASSIGNED: RefCell<Vec<(Pubkey, Pubkey)>> = const { RefCell::new(Vec::new()) };
+  static SYSTEM_DEBITS: RefCell<BTreeMap<Pubkey, u64>> = const { RefCell::new(BTreeMap::new()) };
+}
+pub const FEED_DESCRIPTION: &str = "SOL / USD";
+
+// System Program errors surfaced to the Program
+const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+const INSUFFICIENT_FUNDS: u32 = 1;
+
+
+// Syscall Stubs: Rent, Clock, System Program (Transfer, Create Account) and Chainlink queries
+struct BankStubs;
+impl SyscallStubs for BankStubs {
+  fn sol_log(&self, _message: &str) {}
+
+  fn sol_log_data(&self, fields: &[&[u8]]) {
+    assert!(TradeEvent::try_from_slice(fields[0]).is_ok(), "Event does not decode");
+  }
+
+  fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+    unsafe { *(var_addr as *mut Rent) = Rent::default() };
+    solana_program::entrypoint::SUCCESS
+  }
+
+  fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+    let unix_timestamp = UNIX_NOW.with(|t| t.get());
+    unsafe { *(var_addr as *mut Clock) = Clock { unix_timestamp, ..Clock::default() } };
+    solana_program::entrypoint::SUCCESS
+  }
+
+  fn sol_invoke_signed(
+    &self,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+  ) -> ProgramResult {
+    if instruction.program_id == system_program::id() {
+      return system_instruction(instruction, account_infos, signers_seeds);
+    }
+
+    // Chainlink: Query tag follows the 8 byte discriminator
+    let data = match instruction.data.get(8) {
+      Some(1) => 8u8.try_to_vec().unwrap(),
+      Some(2) => FEED_DESCRIPTION.to_string().try_to_vec().unwrap(),
+      Some(4) => Round {
+        round_id: (UNIX_NOW.with(|t| t.get()) / 10) as u32, // New round every 10 seconds
+        slot: 0,
+        timestamp: UNIX_NOW.with(|t| t.get()) as u32,
+        answer: FEED_PRICE.with(|p| p.get()),
+      }.try_to_vec().unwrap(),
+      _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    RETURN_DATA.with(|r| *r.borrow_mut() = Some((instruction.program_id, data)));
+    Ok(())
+  }
+
+  fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+    RETURN_DATA.with(|r| r.borrow().clone())
+  }
+}
+
+// System Program Transfer and Create Account with the runtime's signer and balance checks
+fn system_instruction(instruction: &Instruction, account_infos: &[AccountInfo], signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+  let program_id = PROGRAM_ID.with(|p| p.get());
+  let find = |index: usize| -> Result<&AccountInfo, ProgramError> {
+    let key = instruction.accounts.get(index).ok_or(ProgramError::NotEnoughAccountKeys)?.pubkey;
+    account_infos.iter().find(|a| *a.key == key).ok_or(ProgramError::NotEnoughAccountKeys)
+  };
+  let signed = |account: &AccountInfo| {
+    account.is_signer || signers_seeds.iter().any(|seeds| {
+      Pubkey::create_program_address(seeds, &program_id) == Ok(*account.key)
+    })
+  };
+  let data = &instruction.data;
+  let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+  let from = find(0)?;
+  let to = find(1)?;
+  if !signed(from) {
+    return Err(ProgramError::MissingRequiredSignature);
+  }
+  if *from.owner != system_program::id() {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  let (lamports, space_owner) = match data[..4] {
+    [0, 0, 0, 0] => {
+      let owner = Pubkey::new(&data[20..52]);
+      (read_u64(4), Some((read_u64(12) as usize, owner)))
+    },
+    [2, 0, 0, 0] => (read_u64(4), None),
+    _ => return Err(ProgramError::InvalidInstructionData),
+  };
+
+  // Create Account: new address must be unused and sign
+  // AccountInfo data cannot grow here, so only addresses reserved by the Bank can be created
+  if let Some((space, owner)) = space_owner {
+    if !signed(to) {
+      return Err(ProgramError::MissingRequiredSignature);
+    }
+    if to.lamports() != 0 || *to.owner != system_program::id() {
+      return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+    }
+    if to.data_len() != space {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    ASSIGNED.with(|a| a.borrow_mut().push((*to.key, owner)));
+  }
+
+  if from.lamports() < lamports {
+    return Err(ProgramError::Custom(INSUFFICIENT_FUNDS));
+  }
+  **from.try_borrow_mut_lamports()? -= lamports;
+  **to.try_borrow_mut_lamports()? += lamports;
+  SYSTEM_DEBITS.with(|d| *d.borrow_mut().entry(*from.key).or_default() += lamports);
+  Ok(())
+}
+
+
+// Account held by the Bank
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+  pub lamports: u64,
+  pub data: Vec<u8>,
+  pub owner: Pubkey,
+}
+
+// In-memory Bank
+// Executes one Instruction at a time, committing account changes only on success (like a Transaction)
+pub struct Bank {
+  pub program_id: Pubkey,
+  pub accounts: BTreeMap<Pubkey, Account>,
+  reserved: BTreeMap<Pubkey, usize>, // Data size of PDAs the Program may create
+}
+
+impl Bank {
+
+  pub fn new(program_id: Pubkey) -> Self {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| { set_syscall_stubs(Box::new(BankStubs)); });
+    PROGRAM_ID.with(|p| p.set(program_id));
+    UNIX_NOW.with(|t| t.set(1_650_000_000));
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+    Self { program_id, accounts: BTreeMap::new(), reserved: BTreeMap::new() }
+  }
+
+  pub fn fund(&mut self, key: Pubkey, lamports: u64) {
+    self.accounts.insert(key, Account { lamports, data: vec![], owner: system_program::id() });
+  }
+
+  pub fn reserve(&mut self, key: Pubkey, space: usize) {
+    self.reserved.insert(key, space);
+  }
+
+  pub fn warp(&self, seconds: u32) {
+    UNIX_NOW.with(|t| t.set(t.get() + seconds as i64));
+  }
+
+  pub fn now(&self) -> u32 {
+    UNIX_NOW.with(|t| t.get()) as u32
+  }
+
+  pub fn move_price(&self, delta: i32) {
+    FEED_PRICE.with(|p| p.set((p.get() + delta as i128).max(1)));
+  }
+
+  pub fn total_lamports(&self) -> u128 {
+    self.accounts.values().map(|a| a.lamports as u128).sum()
+  }
+
+  // Existing Account or an empty System Account (with reserved space for PDAs)
+  fn load(&self, key: &Pubkey) -> Account {
+    self.accounts.get(key).cloned().unwrap_or_else(|| Account {
+      lamports: 0,
+      data: vec![0; self.reserved.get(key).copied().unwrap_or(0)],
+      owner: system_program::id(),
+    })
+  }
+
+  // Run an Instruction against the Program and commit on success
+  pub fn execute(&mut self, instruction: &Instruction) -> ProgramResult {
+    ASSIGNED.with(|a| a.borrow_mut().clear());
+    SYSTEM_DEBITS.with(|d| d.borrow_mut().clear());
+
+    // Unique Accounts (duplicates share one AccountInfo like the runtime)
+    let mut keys: Vec<Pubkey> = Vec::new();
+    for meta in &instruction.accounts {
+      if !keys.contains(&meta.pubkey) {
+        keys.push(meta.pubkey);
+      }
+    }
+    let flags: Vec<(bool, bool)> = keys.iter().map(|key| {
+      let metas = instruction.accounts.iter().filter(|m| m.pubkey == *key);
+      let mut flags = (false, false);
+      for meta in metas {
+        flags.0 |= meta.is_signer;
+        flags.1 |= meta.is_writable;
+      }
+      flags
+    }).collect();
+    let before: Vec<Account> = keys.iter().map(|key| self.load(key)).collect();
+    let mut working = before.clone();
+
+    let result = {
+      let infos: Vec<AccountInfo> = keys.iter().zip(working.iter_mut()).zip(flags.iter())
+        .map(|((key, account), (is_signer, is_writable))| AccountInfo::new(
+          key,
+          *is_signer,
+          *is_writable,
+          &mut account.lamports,
+          &mut account.data,
+          &account.owner,
+          false,
+          Epoch::default(),
+        ))
+        .collect();
+      let account_infos: Vec<AccountInfo> = instruction.accounts.iter()
+        .map(|meta| infos[keys.iter().position(|k| *k == meta.pubkey).unwrap()].clone())
+        .collect();
+      Processor::process(&self.program_id, &account_infos, &instruction.data)
+    };
+    result?;
+
+    // Apply Create Account owners
+    for (key, owner) in ASSIGNED.with(|a| a.borrow().clone()) {
+      let index = keys.iter().position(|k| *k == key).unwrap();
+      working[index].owner = owner;
+    }
+
+    // Runtime rules: read-only Accounts unchanged, only the owner debits lamports or writes data
+    let system_debits = SYSTEM_DEBITS.with(|d| d.borrow().clone());
+    for (index, key) in keys.iter().enumerate() {
+      let (old, new) = (&before[index], &working[index]);
+      if !flags[index].1 {
+        assert_eq!(old, new, "Read-only account {} modified", key);
+      }
+      if old.owner != self.program_id {
+        let debited = old.lamports.saturating_sub(new.lamports);
+        assert!(debited <= system_debits.get(key).copied().unwrap_or(0), "Foreign account {} debited", key);
+        if old.owner == new.owner {
+          assert_eq!(old.data, new.data, "Foreign account {} data modified", key);
+        }
+      }
+    }
+
+    // Commit (zero lamport Accounts are purged)
+    for (key, account) in keys.into_iter().zip(working) {
+      if account.lamports == 0 {
+        self.accounts.remove(&key);
+      } else {
+        self.accounts.insert(key, account);
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,2 @@
+// In-memory Bank and Syscall Stubs shared by the Processor fuzz targets
+pub mod bank;
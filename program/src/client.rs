@@ -0,0 +1,349 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+  borsh::try_from_slice_unchecked,
+  instruction::{AccountMeta, Instruction},
+  program_error::ProgramError,
+  pubkey::Pubkey,
+  system_program,
+};
+use crate::instruction::{
+  TradeInstruction, Make, Take, Deposit, Withdraw, SessionKey, Pause, ListMarket, MarketHalt, ProposeAdmin,
+  CreateMultisig, SetResolver, ResolveTrade, ClaimBatch,
+};
+use crate::state::{User, Trade, Config, Market, Multisig, Observations};
+use crate::pda::{
+  find_user_address, find_trade_address, find_config_address, find_market_address, find_multisig_address,
+  find_observations_address, find_program_data_address,
+};
+
+
+// ACCOUNT DECODERS
+pub fn decode_user(data: &[u8]) -> Result<User, ProgramError> {
+  Ok(User::try_from_slice(data)?)
+}
+
+pub fn decode_trade(data: &[u8]) -> Result<Trade, ProgramError> {
+  try_from_slice_unchecked::<Trade>(data).map_err(|e| e.into())
+}
+
+pub fn decode_config(data: &[u8]) -> Result<Config, ProgramError> {
+  Ok(Config::try_from_slice(data)?)
+}
+
+pub fn decode_market(data: &[u8]) -> Result<Market, ProgramError> {
+  try_from_slice_unchecked::<Market>(data).map_err(|e| e.into())
+}
+
+pub fn decode_multisig(data: &[u8]) -> Result<Multisig, ProgramError> {
+  try_from_slice_unchecked::<Multisig>(data).map_err(|e| e.into())
+}
+
+pub fn decode_observations(data: &[u8]) -> Result<Observations, ProgramError> {
+  try_from_slice_unchecked::<Observations>(data).map_err(|e| e.into())
+}
+
+
+// ORACLE
+// Chainlink Price Feed and Program passed to Make, Take, Claim and RecordObservation
+#[derive(Debug, Clone, Copy)]
+pub struct Oracle {
+  pub feed: Pubkey,
+  pub program: Pubkey,
+}
+
+impl Oracle {
+  // Chainlink Store Program (Devnet and Mainnet)
+  pub fn chainlink(feed: Pubkey) -> Self {
+    Self { feed, program: chainlink_solana::ID }
+  }
+}
+
+
+// INSTRUCTION BUILDERS
+fn build(program_id: &Pubkey, accounts: Vec<AccountMeta>, instruction: TradeInstruction) -> Instruction {
+  Instruction { program_id: *program_id, accounts, data: instruction.pack() }
+}
+
+// Admin is a signing Wallet, or a Multisig Account followed by its signing members
+fn admin_metas(admin: &Pubkey, multisig_signers: &[Pubkey]) -> Vec<AccountMeta> {
+  let mut metas = vec![AccountMeta::new_readonly(*admin, multisig_signers.is_empty())];
+  metas.extend(multisig_signers.iter().map(|signer| AccountMeta::new_readonly(*signer, true)));
+  metas
+}
+
+// Admin Instructions: Admin, Config, [accounts], Multisig Signers
+fn admin_instruction(
+  program_id: &Pubkey,
+  admin: &Pubkey,
+  multisig_signers: &[Pubkey],
+  accounts: Vec<AccountMeta>,
+  instruction: TradeInstruction,
+) -> Instruction {
+  let mut metas = admin_metas(admin, multisig_signers);
+  let signers = metas.split_off(1);
+  metas.push(AccountMeta::new(find_config_address(program_id).0, false));
+  metas.extend(accounts);
+  metas.extend(signers);
+  build(program_id, metas, instruction)
+}
+
+pub fn create_user(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*authority, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+  ], TradeInstruction::CreateUser)
+}
+
+// Sweeps the full free balance
+// Payouts of the settled Trades are collected first (see Trade::uncollected)
+pub fn withdraw_balance(program_id: &Pubkey, authority: &Pubkey, trades: &[Pubkey]) -> Instruction {
+  build(program_id, withdraw_metas(program_id, authority, trades), TradeInstruction::WithdrawBalance)
+}
+
+// Signer is the Authority or one of its Session Keys
+pub fn make_trade(program_id: &Pubkey, signer: &Pubkey, authority: &Pubkey, oracle: &Oracle, make: Make) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*signer, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new(find_trade_address(program_id, &make.slug, authority).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new_readonly(oracle.feed, false),
+    AccountMeta::new_readonly(oracle.program, false),
+    AccountMeta::new_readonly(find_market_address(program_id, &make.symbol).0, false),
+    AccountMeta::new_readonly(find_config_address(program_id).0, false),
+  ], TradeInstruction::MakeTrade(make))
+}
+
+// Signer is the Taker Authority or one of its Session Keys
+pub fn take_trade(
+  program_id: &Pubkey,
+  signer: &Pubkey,
+  authority: &Pubkey,
+  trade: &Pubkey,
+  symbol: &str,
+  oracle: &Oracle,
+  take: Take,
+) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*signer, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new(*trade, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new_readonly(oracle.feed, false),
+    AccountMeta::new_readonly(oracle.program, false),
+    AccountMeta::new_readonly(find_market_address(program_id, symbol).0, false),
+    AccountMeta::new_readonly(find_config_address(program_id).0, false),
+  ], TradeInstruction::TakeTrade(take))
+}
+
+// Observations Account is derived from the decoded Trade Symbol
+pub fn claim(program_id: &Pubkey, claimer: &Pubkey, trade: &Pubkey, trade_state: &Trade, oracle: &Oracle) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new_readonly(*claimer, true),
+    AccountMeta::new(*trade, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new_readonly(oracle.feed, false),
+    AccountMeta::new_readonly(oracle.program, false),
+    AccountMeta::new_readonly(find_observations_address(program_id, &trade_state.symbol).0, false),
+    AccountMeta::new_readonly(find_config_address(program_id).0, false),
+  ], TradeInstruction::Claim)
+}
+
+// Trades of one Symbol claimed against a single Chainlink round (live or observed)
+pub fn claim_batch(
+  program_id: &Pubkey,
+  claimer: &Pubkey,
+  symbol: &str,
+  trades: &[Pubkey],
+  oracle: &Oracle,
+  round_id: u32,
+) -> Instruction {
+  let mut metas = vec![
+    AccountMeta::new_readonly(*claimer, true),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new_readonly(oracle.feed, false),
+    AccountMeta::new_readonly(oracle.program, false),
+    AccountMeta::new_readonly(find_observations_address(program_id, symbol).0, false),
+  ];
+  metas.extend(trades.iter().map(|trade| AccountMeta::new(*trade, false)));
+  metas.push(AccountMeta::new_readonly(find_config_address(program_id).0, false));
+  build(program_id, metas, TradeInstruction::ClaimBatch(ClaimBatch { round_id }))
+}
+
+pub fn deposit(program_id: &Pubkey, authority: &Pubkey, amount: u64) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*authority, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+  ], TradeInstruction::Deposit(Deposit { amount }))
+}
+
+pub fn withdraw(program_id: &Pubkey, authority: &Pubkey, amount: u64, trades: &[Pubkey]) -> Instruction {
+  build(program_id, withdraw_metas(program_id, authority, trades), TradeInstruction::Withdraw(Withdraw { amount }))
+}
+
+// Withdraw: Authority, User, Trades to collect, Config
+fn withdraw_metas(program_id: &Pubkey, authority: &Pubkey, trades: &[Pubkey]) -> Vec<AccountMeta> {
+  let mut metas = vec![
+    AccountMeta::new(*authority, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+  ];
+  metas.extend(trades.iter().map(|trade| AccountMeta::new(*trade, false)));
+  metas.push(AccountMeta::new_readonly(find_config_address(program_id).0, false));
+  metas
+}
+
+pub fn close_user(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*authority, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new_readonly(find_config_address(program_id).0, false),
+  ], TradeInstruction::CloseUser)
+}
+
+pub fn register_session_key(program_id: &Pubkey, authority: &Pubkey, session: SessionKey) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new_readonly(*authority, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+  ], TradeInstruction::RegisterSessionKey(session))
+}
+
+// Signer is the Maker Authority or one of its Session Keys
+pub fn cancel_trade(program_id: &Pubkey, signer: &Pubkey, authority: &Pubkey, trade: &Pubkey) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new_readonly(*signer, true),
+    AccountMeta::new(find_user_address(program_id, authority).0, false),
+    AccountMeta::new(*trade, false),
+  ], TradeInstruction::CancelTrade)
+}
+
+// The Program's Upgrade Authority becomes Admin
+pub fn initialize_config(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*admin, true),
+    AccountMeta::new(find_config_address(program_id).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new_readonly(find_program_data_address(program_id).0, false),
+  ], TradeInstruction::InitializeConfig)
+}
+
+pub fn set_pause(program_id: &Pubkey, admin: &Pubkey, multisig_signers: &[Pubkey], flags: u8) -> Instruction {
+  admin_instruction(program_id, admin, multisig_signers, vec![], TradeInstruction::SetPause(Pause { flags }))
+}
+
+pub fn list_market(
+  program_id: &Pubkey,
+  admin: &Pubkey,
+  multisig_signers: &[Pubkey],
+  payer: &Pubkey,
+  market: ListMarket,
+) -> Instruction {
+  admin_instruction(program_id, admin, multisig_signers, vec![
+    AccountMeta::new(find_market_address(program_id, &market.symbol).0, false),
+    AccountMeta::new(find_observations_address(program_id, &market.symbol).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+    AccountMeta::new(*payer, true),
+  ], TradeInstruction::ListMarket(market))
+}
+
+// Permissionless crank (any fee payer)
+pub fn record_observation(program_id: &Pubkey, symbol: &str, oracle: &Oracle) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(find_observations_address(program_id, symbol).0, false),
+    AccountMeta::new_readonly(find_market_address(program_id, symbol).0, false),
+    AccountMeta::new_readonly(oracle.feed, false),
+    AccountMeta::new_readonly(oracle.program, false),
+  ], TradeInstruction::RecordObservation)
+}
+
+pub fn set_market_halt(
+  program_id: &Pubkey,
+  admin: &Pubkey,
+  multisig_signers: &[Pubkey],
+  symbol: &str,
+  halted: bool,
+) -> Instruction {
+  admin_instruction(program_id, admin, multisig_signers, vec![
+    AccountMeta::new(find_market_address(program_id, symbol).0, false),
+  ], TradeInstruction::SetMarketHalt(MarketHalt { halted }))
+}
+
+pub fn propose_admin(program_id: &Pubkey, admin: &Pubkey, multisig_signers: &[Pubkey], new_admin: &Pubkey) -> Instruction {
+  admin_instruction(program_id, admin, multisig_signers, vec![], TradeInstruction::ProposeAdmin(ProposeAdmin {
+    new_admin: *new_admin,
+  }))
+}
+
+// Pending Admin is a signing Wallet, or a Multisig Account followed by its signing members
+pub fn accept_admin(program_id: &Pubkey, pending_admin: &Pubkey, multisig_signers: &[Pubkey]) -> Instruction {
+  admin_instruction(program_id, pending_admin, multisig_signers, vec![], TradeInstruction::AcceptAdmin)
+}
+
+pub fn create_multisig(program_id: &Pubkey, payer: &Pubkey, m: u8, signers: Vec<Pubkey>) -> Instruction {
+  build(program_id, vec![
+    AccountMeta::new(*payer, true),
+    AccountMeta::new(find_multisig_address(program_id, payer).0, false),
+    AccountMeta::new_readonly(system_program::id(), false),
+  ], TradeInstruction::CreateMultisig(CreateMultisig { m, signers }))
+}
+
+pub fn set_resolver(program_id: &Pubkey, admin: &Pubkey, multisig_signers: &[Pubkey], resolver: &Pubkey) -> Instruction {
+  admin_instruction(program_id, admin, multisig_signers, vec![], TradeInstruction::SetResolver(SetResolver {
+    resolver: *resolver,
+  }))
+}
+
+// Resolver is the Config Resolver Wallet, or the Admin (Wallet or Multisig Account followed by its signing members)
+pub fn resolve_trade(
+  program_id: &Pubkey,
+  resolver: &Pubkey,
+  multisig_signers: &[Pubkey],
+  trade: &Pubkey,
+  resolve: ResolveTrade,
+) -> Instruction {
+  admin_instruction(program_id, resolver, multisig_signers, vec![
+    AccountMeta::new(*trade, false),
+  ], TradeInstruction::ResolveTrade(resolve))
+}
+
+
+// TEST MODULE
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  use crate::state::{ContractSize, Direction, Duration};
+
+  #[test]
+  fn test_builders_round_trip() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let oracle = Oracle::chainlink(Pubkey::new_unique());
+    let make = Make {
+      symbol: String::from("SOL / USD"),
+      slug: String::from("trade1"),
+      contract_size: ContractSize::Medium,
+      direction: Direction::Long,
+      duration: Duration::FiveMinutes,
+      allowed_taker: Pubkey::default(),
+      reference_price: 0,
+      max_deviation_bps: 0,
+    };
+
+    // Make: tag 2, Trade PDA seeded by Authority, Config last
+    let ix = make_trade(&program_id, &authority, &authority, &oracle, make);
+    assert_eq!(ix.data[0], 2);
+    assert!(matches!(TradeInstruction::unpack(&ix.data).unwrap(), TradeInstruction::MakeTrade(m) if m.slug == "trade1"));
+    assert_eq!(ix.accounts[2].pubkey, find_trade_address(&program_id, "trade1", &authority).0);
+    assert_eq!(ix.accounts.last().unwrap().pubkey, find_config_address(&program_id).0);
+
+    // Multisig Admin: Admin does not sign, members sign after the fixed accounts
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = set_pause(&program_id, &authority, &members, 1);
+    assert_eq!(ix.data, vec![10, 1]);
+    assert!(!ix.accounts[0].is_signer);
+    assert_eq!(ix.accounts[1].pubkey, find_config_address(&program_id).0);
+    assert!(ix.accounts[2..].iter().all(|meta| meta.is_signer));
+  }
+}
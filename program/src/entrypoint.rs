@@ -1,11 +1,13 @@
 use solana_program::{
-  account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey
+  account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey
 };
 use crate::processor::Processor;
 
 
 // PROGRAM ENTRYPOINT
-entrypoint!(process_instruction);
+// Clients depend on the crate with no-entrypoint so only the Program exports the symbol
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
 
 // PROGRAM FUNCTION
 pub fn process_instruction(
@@ -22,86 +24,1553 @@ pub fn process_instruction(
 mod test {
   use super::*;
   use borsh::{BorshDeserialize, BorshSerialize};
-  use solana_program::clock::Epoch;
-  use std::mem;
+  use chainlink_solana::Round;
+  use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState}, clock::{Clock, Epoch}, instruction::Instruction, program_stubs::{set_syscall_stubs, SyscallStubs},
+    program_error::ProgramError, pubkey::MAX_SEED_LEN, rent::Rent, system_program, borsh::try_from_slice_unchecked,
+  };
+  use std::cell::{Cell, RefCell};
+  use std::sync::Once;
 
-  use crate::state::{User, Trade};
-  use crate::instruction::{Make, Take};
+  use crate::error::TradeError;
+  use crate::event::TradeEvent;
+  use crate::pda::{
+    find_user_address, find_trade_address, find_config_address, find_market_address, find_multisig_address,
+    find_observations_address, find_program_data_address,
+  };
+  use crate::state::{
+    User, Trade, Config, Market, Multisig, Observations, Observation, ContractSize, Direction, Duration, OrderStatus,
+    Outcome, Party, MAX_OBSERVATIONS, MAX_REASON_LEN, SESSION_SCOPE_MAKE, PAUSE_MAKE,
+  };
+  use crate::instruction::{
+    Make, Take, Deposit, Withdraw, SessionKey, Pause, ListMarket, MarketHalt, ProposeAdmin, CreateMultisig,
+    SetResolver, Resolution, ResolveTrade, ClaimBatch,
+  };
+
+  // Mocked Chainlink Feed and Clock (per test thread)
+  thread_local! {
+    static UNIX_NOW: Cell<i64> = const { Cell::new(1_650_000_000) };
+    static FEED_PRICE: Cell<i128> = const { Cell::new(3_000_000_000) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+    static EVENTS: RefCell<Vec<TradeEvent>> = const { RefCell::new(Vec::new()) };
+  }
+  const FEED_DESCRIPTION: &str = "SOL / USD";
+
+  // Syscall Stubs: Rent, Clock, System Program transfers and Chainlink queries
+  struct TestStubs;
+  impl SyscallStubs for TestStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+      unsafe { *(var_addr as *mut Rent) = Rent::default() };
+      solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+      let unix_timestamp = UNIX_NOW.with(|t| t.get());
+      unsafe { *(var_addr as *mut Clock) = Clock { unix_timestamp, ..Clock::default() } };
+      solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_invoke_signed(
+      &self,
+      instruction: &Instruction,
+      account_infos: &[AccountInfo],
+      _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+
+      // System Program: Transfer moves lamports, Create Account is pre-allocated by the test
+      if instruction.program_id == system_program::id() {
+        if instruction.data[0] == 2 {
+          let amount = u64::from_le_bytes(instruction.data[4..12].try_into().unwrap());
+          let from = account_infos.iter().find(|a| *a.key == instruction.accounts[0].pubkey).unwrap();
+          let to = account_infos.iter().find(|a| *a.key == instruction.accounts[1].pubkey).unwrap();
+          **from.try_borrow_mut_lamports()? -= amount;
+          **to.try_borrow_mut_lamports()? += amount;
+        }
+        return Ok(());
+      }
+
+      // Chainlink: Query tag follows the 8 byte discriminator
+      let data = match instruction.data[8] {
+        1 => 8u8.try_to_vec().unwrap(),
+        2 => FEED_DESCRIPTION.to_string().try_to_vec().unwrap(),
+        4 => Round {
+          round_id: (UNIX_NOW.with(|t| t.get()) / 10) as u32, // New round every 10 seconds
+          slot: 0,
+          timestamp: UNIX_NOW.with(|t| t.get()) as u32,
+          answer: FEED_PRICE.with(|p| p.get()),
+        }.try_to_vec().unwrap(),
+        _ => return Err(ProgramError::InvalidInstructionData),
+      };
+      RETURN_DATA.with(|r| *r.borrow_mut() = Some((instruction.program_id, data)));
+      Ok(())
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+      RETURN_DATA.with(|r| r.borrow().clone())
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+      let event = TradeEvent::try_from_slice(fields[0]).unwrap();
+      EVENTS.with(|e| e.borrow_mut().push(event));
+    }
+  }
+
+  fn setup() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| { set_syscall_stubs(Box::new(TestStubs)); });
+  }
+
+  // Owned Account Data for building AccountInfo
+  struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+  }
+
+  impl TestAccount {
+    fn new(key: Pubkey, owner: Pubkey, lamports: u64, space: usize, is_signer: bool) -> Self {
+      Self { key, owner, lamports, data: vec![0; space], is_signer, is_writable: true }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+      AccountInfo::new(
+        &self.key, // Public key of account
+        self.is_signer, // is_signer
+        self.is_writable, // is_writable
+        &mut self.lamports, // lamports
+        &mut self.data, // data
+        &self.owner, // Program owner
+        false, // executable
+        Epoch::default(), // epoch next owing rent
+      )
+    }
+  }
+
+  // Wallet and matching User PDA holding rent plus a free balance
+  fn user_accounts(program_id: &Pubkey, free_balance: u64) -> (TestAccount, TestAccount) {
+    let authority = TestAccount::new(Pubkey::new_unique(), system_program::id(), 10_000_000_000, 0, true);
+    let (user_pda, user_bump) = find_user_address(program_id, &authority.key);
+    let lamports = Rent::default().minimum_balance(User::LEN) + free_balance;
+    let mut user = TestAccount::new(user_pda, *program_id, lamports, User::LEN, false);
+    let mut user_state = User::try_from_slice(&user.data).unwrap();
+    user_state.free_balance = free_balance;
+    user_state.authority = authority.key;
+    user_state.bump = user_bump;
+    user_state.serialize(&mut &mut user.data[..]).unwrap();
+    (authority, user)
+  }
+
+  // Trade PDA (created by the stubbed System Program call)
+  fn trade_account(program_id: &Pubkey, authority: &Pubkey, slug: &str) -> TestAccount {
+    let (trade_pda, _trade_bump) = find_trade_address(program_id, slug, authority);
+    TestAccount::new(trade_pda, *program_id, Rent::default().minimum_balance(Trade::LEN), Trade::LEN, false)
+  }
+
+  fn make_ix(slug: &str, direction: Direction) -> Vec<u8> {
+    let make = Make {
+      symbol: String::from(FEED_DESCRIPTION),
+      slug: String::from(slug),
+      contract_size: ContractSize::Medium, // 1 Sol
+      direction,
+      duration: Duration::FiveMinutes,
+      allowed_taker: Pubkey::default(),
+      reference_price: 0,
+      max_deviation_bps: 0,
+    };
+    ix_data(2, &make)
+  }
+
+  fn take_ix(direction: Direction) -> Vec<u8> {
+    ix_data(3, &Take { direction, min_price: 0, max_price: 0 })
+  }
+
+  fn ix_data<T: BorshSerialize>(tag: u8, payload: &T) -> Vec<u8> {
+    let mut instruction_data = vec![tag];
+    instruction_data.append(&mut payload.try_to_vec().unwrap());
+    instruction_data
+  }
+
+  // Shared Accounts: System Program, Chainlink, listed Market and Config
+  struct Fixture {
+    program_id: Pubkey,
+    admin: TestAccount,
+    system: TestAccount,
+    feed: TestAccount,
+    chainlink: TestAccount,
+    market: TestAccount,
+    observations: TestAccount,
+    config: TestAccount,
+  }
+
+  impl Fixture {
+    fn new() -> Self {
+      setup();
+      let program_id = Pubkey::new_unique();
+      let rent = Rent::default();
+      let admin = TestAccount::new(Pubkey::new_unique(), system_program::id(), 10_000_000_000, 0, true);
+
+      // Config PDA
+      let (config_pda, config_bump) = find_config_address(&program_id);
+      let mut config = TestAccount::new(config_pda, program_id, rent.minimum_balance(Config::LEN), Config::LEN, false);
+      Config {
+        admin: admin.key,
+        pause_flags: 0,
+        bump: config_bump,
+        pending_admin: Pubkey::default(),
+        resolver: Pubkey::default(),
+      }.serialize(&mut &mut config.data[..]).unwrap();
+
+      // Market PDA
+      let (market_pda, market_bump) = find_market_address(&program_id, FEED_DESCRIPTION);
+      let mut market = TestAccount::new(market_pda, program_id, rent.minimum_balance(Market::LEN), Market::LEN, false);
+      Market {
+        symbol: String::from(FEED_DESCRIPTION),
+        halted: false,
+        bump: market_bump,
+        min_duration: 60,
+        max_duration: 24 * 60 * 60,
+        expiry_interval: 60 * 60, // Top of the Hour
+        expiry_offset: 0,
+        tie_outcome: Outcome::Void,
+        late_outcome: Outcome::Void,
+        claim_window: 24 * 60 * 60,
+        dust_recipient: Party::Maker,
+        challenge_window: 0,
+        twap_window: 0,
+        max_observation_age: 0,
+      }.serialize(&mut &mut market.data[..]).unwrap();
+
+      // Observations PDA
+      let (observations_pda, observations_bump) = find_observations_address(&program_id, FEED_DESCRIPTION);
+      let lamports = rent.minimum_balance(Observations::LEN);
+      let mut observations = TestAccount::new(observations_pda, program_id, lamports, Observations::LEN, false);
+      Observations { bump: observations_bump, head: 0, samples: vec![] }.serialize(&mut &mut observations.data[..]).unwrap();
+
+      Self {
+        program_id,
+        admin,
+        system: TestAccount::new(system_program::id(), Pubkey::default(), 0, 0, false),
+        feed: TestAccount::new(Pubkey::new_unique(), chainlink_solana::ID, 0, 0, false),
+        chainlink: TestAccount::new(chainlink_solana::ID, Pubkey::default(), 0, 0, false),
+        market,
+        observations,
+        config,
+      }
+    }
+
+    // Make and Take: Signer, User, Trade, System, Chainlink Feed, Chainlink Program, Market, Config
+    fn trade_accounts<'a>(
+      &'a mut self,
+      signer: &'a mut TestAccount,
+      user: &'a mut TestAccount,
+      trade: &'a mut TestAccount,
+    ) -> Vec<AccountInfo<'a>> {
+      vec![
+        signer.info(), user.info(), trade.info(), self.system.info(), self.feed.info(), self.chainlink.info(),
+        self.market.info(), self.config.info(),
+      ]
+    }
+
+    // Claim: Claimer, Trade, System, Chainlink Feed, Chainlink Program, Observations, Config
+    fn claim_accounts<'a>(&'a mut self, claimer: &'a mut TestAccount, trade: &'a mut TestAccount) -> Vec<AccountInfo<'a>> {
+      vec![
+        claimer.info(), trade.info(), self.system.info(), self.feed.info(), self.chainlink.info(),
+        self.observations.info(), self.config.info(),
+      ]
+    }
+
+    // Withdraw and Close: Authority, User, Config
+    fn user_accounts<'a>(&'a mut self, authority: &'a mut TestAccount, user: &'a mut TestAccount) -> Vec<AccountInfo<'a>> {
+      vec![authority.info(), user.info(), self.config.info()]
+    }
+
+    // Withdraw nothing, collecting the Payout of a settled Trade: Authority, User, Trade, Config
+    fn collect(&mut self, authority: &mut TestAccount, user: &mut TestAccount, trade: &mut TestAccount) -> ProgramResult {
+      let accounts = vec![authority.info(), user.info(), trade.info(), self.config.info()];
+      process_instruction(&self.program_id, &accounts, &ix_data(6, &Withdraw { amount: 0 }))
+    }
+
+    // Admin: Admin, Config, Market
+    fn admin_accounts(&mut self) -> Vec<AccountInfo<'_>> {
+      vec![self.admin.info(), self.config.info(), self.market.info()]
+    }
+  }
 
   #[test]
   fn test_sanity() {
+    setup();
 
     // Program Id
-    let program_id = Pubkey::default();
-
-    // Account User
-    let key = Pubkey::default();
-    let owner = Pubkey::default();
-    let mut lamports: u64 = 5000000000;
-    let mut data = vec![0; User::LEN];
-    let account = AccountInfo::new(
-      &key, // Public key of account
-      true, // is_signer
-      true, // is_writable
-      &mut lamports, // lamports
-      &mut data, // data
-      &owner, // Program owner
-      false, // executable
-      Epoch::default(), // epoch next owing rent
+    let program_id = Pubkey::new_unique();
+
+    // Account User and System Account
+    let (mut authority, mut user) = user_accounts(&program_id, 0);
+    user.data.fill(0); // Freshly created by the System Program
+    let mut system = TestAccount::new(system_program::id(), Pubkey::default(), 0, 0, false);
+    let accounts = vec![authority.info(), user.info(), system.info()];
+
+    // Build Instruction 0 (Create User)
+    let instruction_data = vec![0];
+
+    // Send Request
+    process_instruction(&program_id, &accounts, &instruction_data).unwrap();
+    drop(accounts);
+
+    // Bump stored for create_program_address checks
+    let user_state = User::try_from_slice(&user.data).unwrap();
+    assert_eq!(user_state.bump, find_user_address(&program_id, &authority.key).1);
+  }
+
+  #[test]
+  fn test_user_stats() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "trade1");
+
+    // Make (Long 1 SOL)
+    let program_id = fx.program_id;
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("trade1", Direction::Long)).unwrap();
+    drop(accounts);
+
+    // Take
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+
+    // Claim after expiry with a higher price (Maker wins)
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    FEED_PRICE.with(|p| p.set(p.get() + 1));
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    fx.collect(&mut maker, &mut maker_user, &mut trade).unwrap();
+    fx.collect(&mut taker, &mut taker_user, &mut trade).unwrap();
+
+    // Check Stats
+    let maker_stats = User::try_from_slice(&maker_user.data).unwrap();
+    assert_eq!(maker_stats.trades_placed, 1);
+    assert_eq!(maker_stats.trades_made, 1);
+    assert_eq!(maker_stats.trades_won, 1);
+    assert_eq!(maker_stats.total_volume, 1_000_000_000);
+    assert_eq!(maker_stats.realized_pnl, 1_000_000_000);
+
+    let taker_stats = User::try_from_slice(&taker_user.data).unwrap();
+    assert_eq!(taker_stats.trades_taken, 1);
+    assert_eq!(taker_stats.trades_lost, 1);
+    assert_eq!(taker_stats.total_volume, 1_000_000_000);
+    assert_eq!(taker_stats.realized_pnl, -1_000_000_000);
+    assert_eq!(maker_stats.free_balance, 3_000_000_000);
+    assert_eq!(maker_stats.locked_balance, 0);
+    assert_eq!(maker_stats.open_positions, 0);
+    assert_eq!(taker_stats.free_balance, 1_000_000_000);
+    assert_eq!(maker_user.lamports, Rent::default().minimum_balance(User::LEN) + 3_000_000_000);
+
+    // Check Events
+    let events = EVENTS.with(|e| e.borrow().clone());
+    assert!(matches!(events[0], TradeEvent::TradeMade { stake: 1_000_000_000, .. }));
+    assert!(matches!(events[1], TradeEvent::TradeTaken { benchmark_price: 3_000_000_000, .. }));
+    assert_eq!(events[2], TradeEvent::TradeSettled {
+      trade: trade.key,
+      winner: 0,
+      closing_price: 3_000_000_001,
+      payout: 2_000_000_000,
+    });
+    assert_eq!(try_from_slice_unchecked::<Trade>(&trade.data).unwrap().order_status, OrderStatus::Settled);
+  }
+
+  #[test]
+  fn test_trade_len() {
+
+    // Longest Slug, Symbol and Reason with a Duration carrying its u32 fill the Trade Account exactly
+    let mut trade_state = try_from_slice_unchecked::<Trade>(&[0; Trade::LEN]).unwrap();
+    trade_state.slug = "s".repeat(MAX_SEED_LEN);
+    trade_state.symbol = "S".repeat(MAX_SEED_LEN);
+    trade_state.duration = Duration::Expiry(u32::MAX);
+    trade_state.resolution_reason = "r".repeat(MAX_REASON_LEN);
+    let mut data = vec![0; Trade::LEN];
+    trade_state.serialize(&mut &mut data[..]).unwrap();
+    assert_eq!(trade_state.try_to_vec().unwrap().len(), Trade::LEN);
+  }
+
+  #[test]
+  fn test_order_status_machine() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "status1");
+    let program_id = fx.program_id;
+
+    // Out of range enums are rejected at unpack
+    let mut invalid = make_ix("status1", Direction::Long);
+    let len = invalid.len();
+    invalid[len - 52] = 2; // Direction (Allowed Taker and Price Protection close the payload)
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &invalid),
+      Err(TradeError::InvalidInstructionValue.into())
     );
+    assert!(process_instruction(&program_id, &accounts, &[3, 2]).is_err());
+
+    // Make and Take
+    process_instruction(&program_id, &accounts, &make_ix("status1", Direction::Long)).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+
+    // InPlay cannot be Cancelled
+    let accounts = vec![maker.info(), maker_user.info(), trade.info()];
+    assert_eq!(process_instruction(&program_id, &accounts, &[13]), Err(TradeError::InvalidTradeForCancel.into()));
+    drop(accounts);
+
+    // Claim after the 24 hour allowance Expires the Trade
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60 + 24 * 60 * 60));
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&trade.data).unwrap().order_status, OrderStatus::Expired);
+
+    // Final states do not move
+    let mut status = OrderStatus::Expired;
+    assert_eq!(status.transition(OrderStatus::Settled), Err(TradeError::InvalidStatusTransition));
+    assert_eq!(status, OrderStatus::Expired);
+    assert!(!OrderStatus::Cancelled.can_transition(OrderStatus::InPlay));
+    assert!(!OrderStatus::Open.can_transition(OrderStatus::Settled));
+    assert!(OrderStatus::InPlay.can_transition(OrderStatus::Voided));
+  }
+
+  #[test]
+  fn test_foreign_trade_account() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "forged1");
+    let program_id = fx.program_id;
+
+    // Make, then copy the Trade into an Account owned by another Program (found by the processor fuzz target)
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("forged1", Direction::Long)).unwrap();
+    drop(accounts);
+    let mut forged = TestAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), trade.lamports, Trade::LEN, false);
+    forged.data.copy_from_slice(&trade.data);
 
-    // Generate Program Derived Address (PDA)
-    let slug = String::from("trade1");
-    let (trade_pda, _trade_bump) = Pubkey::find_program_address(
-      &[b"trade".as_ref(), slug.as_ref(), Pubkey::default().as_ref()],
-      &program_id 
+    // Take and Claim reject it
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut forged);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Short)),
+      Err(ProgramError::IncorrectProgramId)
     );
+    drop(accounts);
+    let accounts = fx.claim_accounts(&mut taker, &mut forged);
+    assert_eq!(process_instruction(&program_id, &accounts, &[4]), Err(ProgramError::IncorrectProgramId));
+    drop(accounts);
 
-    // Account Trade PDA
-    let key_pda = trade_pda;
-    let owner_pda = Pubkey::default();
-    let mut lamports_pda: u64 = 0;
-    let mut data_pda = vec![0; Trade::LEN];
-    let account_pda = AccountInfo::new(
-      &key_pda, // Public key of account
-      true, // is_signer
-      true, // is_writable
-      &mut lamports_pda, // lamports
-      &mut data_pda, // data
-      &owner_pda, // Program owner
-      false, // executable
-      Epoch::default(), // epoch next owing rent
+    // A Program owned copy at another address is not the Trade PDA, even when its data names that address
+    let mut copied = TestAccount::new(Pubkey::new_unique(), program_id, trade.lamports, Trade::LEN, false);
+    let mut copied_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    copied_state.trade_account = copied.key;
+    copied_state.serialize(&mut &mut copied.data[..]).unwrap();
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut copied);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Short)),
+      Err(TradeError::InvalidTradeAccount.into())
     );
+    drop(accounts);
+    let accounts = fx.claim_accounts(&mut taker, &mut copied);
+    assert_eq!(process_instruction(&program_id, &accounts, &[4]), Err(TradeError::InvalidTradeAccount.into()));
+  }
+
+  #[test]
+  fn test_take_guards() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut other, mut other_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "otc1");
+    let program_id = fx.program_id;
+    let short = take_ix(Direction::Short);
 
-    // Add to Accounts Vector - Authority, PDA, System Account
-    let system_account = account.clone();
-    let accounts = vec![account, account_pda, system_account];
-
-    // Build Instruction 0 (Make)
-    let ix_struct = Make {
-      symbol: String::from("SOLUSD"),
-      slug: slug,
-      contract_size: 1, // 1 Sol
-      direction: 0, // Long
-      duration: 0, // 5Min
+    // Make (Long, OTC with Taker)
+    let make = Make {
+      symbol: String::from(FEED_DESCRIPTION),
+      slug: String::from("otc1"),
+      contract_size: ContractSize::Medium,
+      direction: Direction::Long,
+      duration: Duration::FiveMinutes,
+      allowed_taker: taker.key,
+      reference_price: 0,
+      max_deviation_bps: 0,
     };
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &ix_data(2, &make)).unwrap();
 
-    // // Build Instruction 1 (Take)
-    // let ix_struct = Take {
-    //   account_ref: Pubkey::default(),
-    // };
+    // Maker cannot Take own Trade
+    assert_eq!(process_instruction(&program_id, &accounts, &short), Err(TradeError::SelfTrade.into()));
+    drop(accounts);
 
-    // Convert Instruction into Bytes
-    let mut instruction = ix_struct.try_to_vec().unwrap();
+    // Other Authority is not the allowed Taker
+    let accounts = fx.trade_accounts(&mut other, &mut other_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &short), Err(TradeError::TakerNotAllowed.into()));
+    drop(accounts);
 
-    // Initialize instruction data with Routing
-    let mut instruction_data: Vec<u8> = Vec::new();
-    instruction_data.push(u8::to_le_bytes(0)[0]); // 0 for Make, 1 for Take
+    // Same Direction as the Maker is rejected, opposite is filled
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Long)),
+      Err(TradeError::InvalidTakerDirection.into())
+    );
+    process_instruction(&program_id, &accounts, &short).unwrap();
+    drop(accounts);
 
-    // Add Maker Instruction to Instruction Data
-    instruction_data.append(&mut instruction);
+    let trade_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    assert_eq!(trade_state.allowed_taker, taker.key);
+    assert_eq!(trade_state.taker, taker_user.key);
+    assert_eq!(trade_state.order_status, OrderStatus::InPlay);
+  }
 
-    // Send Request
-    process_instruction(&program_id, &accounts, &instruction_data).unwrap();
+  #[test]
+  fn test_price_protection() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "price1");
+    let program_id = fx.program_id;
+    let make = |reference_price: i128| Make {
+      symbol: String::from(FEED_DESCRIPTION),
+      slug: String::from("price1"),
+      contract_size: ContractSize::Medium,
+      direction: Direction::Long,
+      duration: Duration::FiveMinutes,
+      allowed_taker: Pubkey::default(),
+      reference_price,
+      max_deviation_bps: 100, // 1%
+    };
+    let take = |min_price: i128, max_price: i128| ix_data(3, &Take { direction: Direction::Short, min_price, max_price });
+
+    // Make (Reference 3050 within 1%, Feed at 3000)
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &ix_data(2, &make(-1))),
+      Err(TradeError::InvalidPriceProtection.into())
+    );
+    process_instruction(&program_id, &accounts, &ix_data(2, &make(3_050_000_000))).unwrap();
+    drop(accounts);
+
+    // Taker Bounds checked first, then the Maker Reference
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &take(2, 1)), Err(TradeError::InvalidPriceProtection.into()));
+    assert_eq!(process_instruction(&program_id, &accounts, &take(0, 2_999_999_999)), Err(TradeError::TakerPriceOutOfBounds.into()));
+    assert_eq!(process_instruction(&program_id, &accounts, &take(3_000_000_001, 0)), Err(TradeError::TakerPriceOutOfBounds.into()));
+    assert_eq!(process_instruction(&program_id, &accounts, &take(0, 0)), Err(TradeError::MakerPriceOutOfBounds.into()));
+
+    // Price moves within both
+    FEED_PRICE.with(|p| p.set(3_040_000_000));
+    process_instruction(&program_id, &accounts, &take(3_000_000_000, 3_100_000_000)).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+
+    let trade_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    assert_eq!(trade_state.benchmark_price, 3_040_000_000);
+    assert_eq!(trade_state.reference_price, 3_050_000_000);
+    assert_eq!(trade_state.order_status, OrderStatus::InPlay);
+  }
+
+  #[test]
+  fn test_durations() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 5_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 5_000_000_000);
+    let program_id = fx.program_id;
+    let make = |slug: &str, duration: Duration| ix_data(2, &Make {
+      symbol: String::from(FEED_DESCRIPTION),
+      slug: String::from(slug),
+      contract_size: ContractSize::Medium,
+      direction: Direction::Long,
+      duration,
+      allowed_taker: Pubkey::default(),
+      reference_price: 0,
+      max_deviation_bps: 0,
+    });
+
+    // Market: 1 Min to 1 Day, Expiries at the top of the Hour
+    let now = UNIX_NOW.with(|t| t.get()) as u32;
+    let expiry = (now / 3600 + 1) * 3600;
+    let mut trade = trade_account(&program_id, &maker.key, "custom1");
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    for (duration, error) in [
+      (Duration::Seconds(59), TradeError::DurationOutOfBounds),
+      (Duration::Seconds(24 * 60 * 60 + 1), TradeError::DurationOutOfBounds),
+      (Duration::Expiry(expiry + 1), TradeError::ExpiryNotOnCalendar),
+      (Duration::Expiry(now / 3600 * 3600), TradeError::DurationOutOfBounds),
+    ] {
+      assert_eq!(process_instruction(&program_id, &accounts, &make("custom1", duration)), Err(error.into()));
+    }
+
+    // Custom length runs from Take
+    process_instruction(&program_id, &accounts, &make("custom1", Duration::Seconds(90))).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&trade.data).unwrap().unix_end, now + 90);
+
+    // Trades on the same Expiry share unix_end whenever Taken
+    let mut first = trade_account(&program_id, &maker.key, "expiry1");
+    let mut second = trade_account(&program_id, &maker.key, "expiry2");
+    let mut late = trade_account(&program_id, &maker.key, "expiry3");
+    for (slug, trade) in [("expiry1", &mut first), ("expiry2", &mut second), ("expiry3", &mut late)] {
+      let accounts = fx.trade_accounts(&mut maker, &mut maker_user, trade);
+      process_instruction(&program_id, &accounts, &make(slug, Duration::Expiry(expiry))).unwrap();
+    }
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut first);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+    UNIX_NOW.with(|t| t.set(t.get() + 600));
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut second);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+
+    // Less than the Market minimum left
+    UNIX_NOW.with(|t| t.set(expiry as i64 - 30));
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut late);
+    assert_eq!(process_instruction(&program_id, &accounts, &take_ix(Direction::Short)), Err(TradeError::DurationOutOfBounds.into()));
+    drop(accounts);
+    UNIX_NOW.with(|t| t.set(now as i64));
+
+    let first_state = try_from_slice_unchecked::<Trade>(&first.data).unwrap();
+    let second_state = try_from_slice_unchecked::<Trade>(&second.data).unwrap();
+    assert_eq!((first_state.unix_end, second_state.unix_end), (expiry, expiry));
+    assert_eq!(second_state.unix_start, now + 600);
+  }
+
+  #[test]
+  fn test_settlement_policy() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let program_id = fx.program_id;
+    let trade_rent = Rent::default().minimum_balance(Trade::LEN);
+
+    // Market: tie to Maker, late Claims to Taker after 10 minutes, dust to Taker
+    let mut market_state = try_from_slice_unchecked::<Market>(&fx.market.data).unwrap();
+    market_state.tie_outcome = Outcome::Maker;
+    market_state.late_outcome = Outcome::Taker;
+    market_state.claim_window = 600;
+    market_state.dust_recipient = Party::Taker;
+    market_state.serialize(&mut &mut fx.market.data[..]).unwrap();
+
+    // Policy fixed at Make
+    let mut tie = trade_account(&program_id, &maker.key, "tie1");
+    let mut late = trade_account(&program_id, &maker.key, "late1");
+    for (slug, trade) in [("tie1", &mut tie), ("late1", &mut late)] {
+      let accounts = fx.trade_accounts(&mut maker, &mut maker_user, trade);
+      process_instruction(&program_id, &accounts, &make_ix(slug, Direction::Long)).unwrap();
+      drop(accounts);
+      let accounts = fx.trade_accounts(&mut taker, &mut taker_user, trade);
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    }
+    let tie_state = try_from_slice_unchecked::<Trade>(&tie.data).unwrap();
+    assert_eq!((tie_state.tie_outcome, tie_state.late_outcome, tie_state.claim_window), (Outcome::Maker, Outcome::Taker, 600));
+
+    // Tie goes to the Maker, dust to the Taker
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    tie.lamports += 3;
+    let accounts = fx.claim_accounts(&mut taker, &mut tie);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    fx.collect(&mut maker, &mut maker_user, &mut tie).unwrap();
+    fx.collect(&mut taker, &mut taker_user, &mut tie).unwrap();
+    assert_eq!(tie.lamports, trade_rent);
+    assert_eq!(User::try_from_slice(&maker_user.data).unwrap().free_balance, 2_000_000_000);
+    assert_eq!(User::try_from_slice(&taker_user.data).unwrap().free_balance, 3);
+
+    // Late Claim goes to the Taker even though the price moved for the Maker
+    UNIX_NOW.with(|t| t.set(t.get() + 600));
+    FEED_PRICE.with(|p| p.set(p.get() + 1));
+    let accounts = fx.claim_accounts(&mut maker, &mut late);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+    fx.collect(&mut maker, &mut maker_user, &mut late).unwrap();
+    fx.collect(&mut taker, &mut taker_user, &mut late).unwrap();
+    assert_eq!(try_from_slice_unchecked::<Trade>(&late.data).unwrap().order_status, OrderStatus::Expired);
+
+    let maker_state = User::try_from_slice(&maker_user.data).unwrap();
+    let taker_state = User::try_from_slice(&taker_user.data).unwrap();
+    assert_eq!((maker_state.trades_won, maker_state.trades_lost), (1, 1));
+    assert_eq!((taker_state.trades_won, taker_state.trades_lost), (1, 1));
+    assert_eq!(taker_state.free_balance, 2_000_000_003);
+    assert_eq!(maker_state.realized_pnl + taker_state.realized_pnl, 3);
+  }
+
+  #[test]
+  fn test_challenge_window() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 3_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 3_000_000_000);
+    let mut resolver = TestAccount::new(Pubkey::new_unique(), system_program::id(), 0, 0, true);
+    let program_id = fx.program_id;
+    let resolve = |resolution: Resolution, reason: &str| ix_data(18, &ResolveTrade { resolution, reason: String::from(reason) });
+
+    // Market: 10 minute challenge window
+    let mut market_state = try_from_slice_unchecked::<Market>(&fx.market.data).unwrap();
+    market_state.challenge_window = 600;
+    market_state.serialize(&mut &mut fx.market.data[..]).unwrap();
+
+    let mut disputed = trade_account(&program_id, &maker.key, "dispute1");
+    let mut voided = trade_account(&program_id, &maker.key, "dispute2");
+    let mut no_oracle = trade_account(&program_id, &maker.key, "oracle1");
+    for (slug, trade) in [("dispute1", &mut disputed), ("dispute2", &mut voided), ("oracle1", &mut no_oracle)] {
+      let accounts = fx.trade_accounts(&mut maker, &mut maker_user, trade);
+      process_instruction(&program_id, &accounts, &make_ix(slug, Direction::Long)).unwrap();
+      drop(accounts);
+      let accounts = fx.trade_accounts(&mut taker, &mut taker_user, trade);
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    }
+
+    // A running Trade cannot be voided
+    let accounts = vec![fx.admin.info(), fx.config.info(), no_oracle.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &resolve(Resolution::Void, "early")),
+      Err(TradeError::InvalidTimeForClaim.into())
+    );
+    drop(accounts);
+
+    // Claim records the Closing Price, payout waits for the window
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    FEED_PRICE.with(|p| p.set(p.get() + 1));
+    for trade in [&mut disputed, &mut voided] {
+      let accounts = fx.claim_accounts(&mut maker, trade);
+      process_instruction(&program_id, &accounts, &[4]).unwrap();
+      assert_eq!(process_instruction(&program_id, &accounts, &[4]), Err(TradeError::ChallengeWindowOpen.into()));
+    }
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+    assert_eq!(try_from_slice_unchecked::<Trade>(&disputed.data).unwrap().order_status, OrderStatus::Pending);
+    assert_eq!(User::try_from_slice(&maker_user.data).unwrap().free_balance, 0);
+
+    // Only the Admin or Resolver can resolve
+    let accounts = vec![maker.info(), fx.config.info(), disputed.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &resolve(Resolution::Void, "spike")),
+      Err(TradeError::NotResolver.into())
+    );
+    drop(accounts);
+    let accounts = vec![fx.admin.info(), fx.config.info()];
+    process_instruction(&program_id, &accounts, &ix_data(17, &SetResolver { resolver: resolver.key })).unwrap();
+    drop(accounts);
+
+    // Resolver corrects a bad print (Taker now wins) and voids a disputed Trade
+    let accounts = vec![resolver.info(), fx.config.info(), disputed.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &resolve(Resolution::ClosingPrice(2_999_999_999), "")),
+      Err(TradeError::InvalidResolution.into())
+    );
+    process_instruction(&program_id, &accounts, &resolve(Resolution::ClosingPrice(2_999_999_999), "feed spike")).unwrap();
+    drop(accounts);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&disputed.data).unwrap().resolution_reason, "feed spike");
+    let accounts = vec![resolver.info(), fx.config.info(), voided.info()];
+    process_instruction(&program_id, &accounts, &resolve(Resolution::Void, "market closed")).unwrap();
+    drop(accounts);
+    fx.collect(&mut maker, &mut maker_user, &mut voided).unwrap();
+    fx.collect(&mut taker, &mut taker_user, &mut voided).unwrap();
+    assert_eq!(try_from_slice_unchecked::<Trade>(&voided.data).unwrap().order_status, OrderStatus::Voided);
+    assert_eq!(voided.lamports, Rent::default().minimum_balance(Trade::LEN));
+    assert_eq!(User::try_from_slice(&maker_user.data).unwrap().free_balance, 1_000_000_000);
+
+    // Oracle down: Resolver supplies the Closing Price of an unclaimed Trade
+    let accounts = vec![resolver.info(), fx.config.info(), no_oracle.info()];
+    process_instruction(&program_id, &accounts, &resolve(Resolution::ClosingPrice(3_000_000_001), "oracle down")).unwrap();
+    drop(accounts);
+    let events = EVENTS.with(|e| e.borrow().clone());
+    assert_eq!(events.last(), Some(&TradeEvent::TradeResolved {
+      trade: no_oracle.key,
+      resolver: resolver.key,
+      voided: false,
+      closing_price: 3_000_000_001,
+      reason: String::from("oracle down"),
+    }));
+
+    // Window closed: no more changes, Claim pays the recorded price
+    UNIX_NOW.with(|t| t.set(t.get() + 600));
+    let accounts = vec![resolver.info(), fx.config.info(), disputed.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &resolve(Resolution::Void, "too late")),
+      Err(TradeError::ChallengeWindowClosed.into())
+    );
+    drop(accounts);
+    for trade in [&mut disputed, &mut no_oracle] {
+      let accounts = fx.claim_accounts(&mut taker, trade);
+      process_instruction(&program_id, &accounts, &[4]).unwrap();
+      drop(accounts);
+      fx.collect(&mut maker, &mut maker_user, trade).unwrap();
+      fx.collect(&mut taker, &mut taker_user, trade).unwrap();
+    }
+
+    let maker_state = User::try_from_slice(&maker_user.data).unwrap();
+    let taker_state = User::try_from_slice(&taker_user.data).unwrap();
+    assert_eq!((maker_state.trades_won, maker_state.trades_lost), (1, 1));
+    assert_eq!((taker_state.trades_won, taker_state.trades_lost), (1, 1));
+    assert_eq!((maker_state.free_balance, taker_state.free_balance), (3_000_000_000, 3_000_000_000));
+    assert_eq!(maker_state.open_positions, 0);
+  }
+
+  #[test]
+  fn test_twap_settlement() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "twap1");
+    let program_id = fx.program_id;
+    let start = UNIX_NOW.with(|t| t.get());
+
+    // Market: settle on the average of the last 2 minutes
+    let mut market_state = try_from_slice_unchecked::<Market>(&fx.market.data).unwrap();
+    market_state.twap_window = 120;
+    market_state.serialize(&mut &mut fx.market.data[..]).unwrap();
+
+    // Make and Take (Long from 3_000_000_000, ends at start + 5 minutes)
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("twap1", Direction::Long)).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+
+    // Crank: before the window, a repeated round, then two rounds inside it
+    for (offset, price, result) in [
+      (100, 3_100_000_000, Ok(())),
+      (100, 3_100_000_000, Err(TradeError::StaleObservation.into())),
+      (200, 2_999_999_000, Ok(())),
+      (300, 2_999_999_400, Ok(())),
+    ] {
+      UNIX_NOW.with(|t| t.set(start + offset));
+      FEED_PRICE.with(|p| p.set(price));
+      let accounts = vec![fx.observations.info(), fx.market.info(), fx.feed.info(), fx.chainlink.info()];
+      assert_eq!(process_instruction(&program_id, &accounts, &[19]), result);
+    }
+    let events = EVENTS.with(|e| e.borrow().clone());
+    assert_eq!(events.last(), Some(&TradeEvent::ObservationRecorded {
+      market: fx.market.key,
+      unix_timestamp: start as u32 + 300,
+      price: 2_999_999_400,
+      round_id: (start as u32 + 300) / 10,
+    }));
+
+    // Claim ignores the live price (Maker would win) and pays the Taker on the average
+    UNIX_NOW.with(|t| t.set(start + 400));
+    FEED_PRICE.with(|p| p.set(3_200_000_000));
+    let accounts = fx.claim_accounts(&mut maker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+    fx.collect(&mut taker, &mut taker_user, &mut trade).unwrap();
+    let trade_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    assert_eq!((trade_state.twap_window, trade_state.closing_price), (120, 2_999_999_200));
+    assert_eq!(User::try_from_slice(&taker_user.data).unwrap().free_balance, 3_000_000_000);
+
+    // Ring buffer overwrites the oldest Observations once full
+    let mut ring = Observations { bump: 0, head: 0, samples: vec![] };
+    for unix_timestamp in 1..=MAX_OBSERVATIONS as u32 + 2 {
+      ring.record(Observation { unix_timestamp, price: unix_timestamp as i128, round_id: unix_timestamp }).unwrap();
+    }
+    assert_eq!((ring.head, ring.samples.len()), (2, MAX_OBSERVATIONS));
+    assert_eq!(ring.latest().unwrap().unix_timestamp, MAX_OBSERVATIONS as u32 + 2);
+    assert_eq!(ring.twap(1, 2), Err(TradeError::NoObservations));
+    assert_eq!(ring.twap(3, 5), Ok(4));
+    assert_eq!(ring.history().first().map(|sample| sample.unix_timestamp), Some(3));
+    assert_eq!(ring.history().last(), ring.latest());
+  }
+
+  #[test]
+  fn test_observed_prices() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "observed1");
+    let program_id = fx.program_id;
+    let start = UNIX_NOW.with(|t| t.get());
+    let observe = |fx: &mut Fixture| {
+      let accounts = vec![fx.observations.info(), fx.market.info(), fx.feed.info(), fx.chainlink.info()];
+      process_instruction(&program_id, &accounts, &[19])
+    };
+
+    // Market: Observations up to a minute old replace live rounds at Claim
+    let mut market_state = try_from_slice_unchecked::<Market>(&fx.market.data).unwrap();
+    market_state.max_observation_age = 60;
+    market_state.serialize(&mut &mut fx.market.data[..]).unwrap();
+
+    // Same round twice is rejected
+    observe(&mut fx).unwrap();
+    UNIX_NOW.with(|t| t.set(start + 5));
+    assert_eq!(observe(&mut fx), Err(TradeError::StaleObservation.into()));
+
+    // Take 30 seconds later benchmarks on the live price, never on the fresh but older Observation
+    UNIX_NOW.with(|t| t.set(start + 30));
+    FEED_PRICE.with(|p| p.set(3_050_000_000));
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("observed1", Direction::Long)).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+    let trade_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    assert_eq!((trade_state.benchmark_price, trade_state.max_observation_age), (3_050_000_000, 60));
+
+    // Claim well after the end settles on the price observed at the end (Maker wins despite the live price)
+    UNIX_NOW.with(|t| t.set(start + 30 + 5 * 60));
+    FEED_PRICE.with(|p| p.set(3_050_000_001));
+    observe(&mut fx).unwrap();
+    UNIX_NOW.with(|t| t.set(start + 30 + 60 * 60));
+    FEED_PRICE.with(|p| p.set(2_000_000_000));
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+    fx.collect(&mut maker, &mut maker_user, &mut trade).unwrap();
+    assert_eq!(try_from_slice_unchecked::<Trade>(&trade.data).unwrap().closing_price, 3_050_000_001);
+    assert_eq!(User::try_from_slice(&maker_user.data).unwrap().free_balance, 3_000_000_000);
+
+    // Charting reads the same history
+    let history = try_from_slice_unchecked::<Observations>(&fx.observations.data).unwrap().history();
+    assert_eq!(history.iter().map(|sample| sample.price).collect::<Vec<_>>(), vec![3_000_000_000, 3_050_000_001]);
+  }
+
+  #[test]
+  fn test_pull_payouts() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 2_000_000_000);
+    let mut trade = trade_account(&fx.program_id, &maker.key, "pull1");
+    let program_id = fx.program_id;
+    let trade_rent = Rent::default().minimum_balance(Trade::LEN);
+
+    // Make, Take and Claim (Maker wins)
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("pull1", Direction::Long)).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    FEED_PRICE.with(|p| p.set(p.get() + 1));
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+
+    // Settlement leaves the Users untouched, the Trade holds both stakes
+    let trade_state = try_from_slice_unchecked::<Trade>(&trade.data).unwrap();
+    assert_eq!((trade_state.settled_outcome, trade_state.maker_payout, trade_state.taker_payout), (Outcome::Maker, 2_000_000_000, 0));
+    assert_eq!(trade.lamports, trade_rent + 2_000_000_000);
+    let maker_state = User::try_from_slice(&maker_user.data).unwrap();
+    assert_eq!((maker_state.free_balance, maker_state.locked_balance, maker_state.open_positions), (1_000_000_000, 1_000_000_000, 1));
+
+    // Uncollected Trade blocks Close, the losing side collects nothing but releases its stake
+    let accounts = fx.user_accounts(&mut taker, &mut taker_user);
+    assert_eq!(process_instruction(&program_id, &accounts, &[7]), Err(TradeError::OpenPositionsExist.into()));
+    drop(accounts);
+    let accounts = vec![taker.info(), taker_user.info(), trade.info(), fx.config.info()];
+    process_instruction(&program_id, &accounts, &[1]).unwrap();
+    assert_eq!(process_instruction(&program_id, &accounts, &[1]), Err(TradeError::NothingToCollect.into()));
+    drop(accounts);
+    let taker_state = User::try_from_slice(&taker_user.data).unwrap();
+    assert_eq!((taker_state.locked_balance, taker_state.open_positions, taker_state.trades_lost), (0, 0, 1));
+    let accounts = fx.user_accounts(&mut taker, &mut taker_user);
+    process_instruction(&program_id, &accounts, &[7]).unwrap();
+    drop(accounts);
+
+    // Winner collects and withdraws in the same instruction
+    let accounts = vec![maker.info(), maker_user.info(), trade.info(), fx.config.info()];
+    process_instruction(&program_id, &accounts, &ix_data(6, &Withdraw { amount: 1_000_000_000 })).unwrap();
+    drop(accounts);
+    assert_eq!(trade.lamports, trade_rent);
+    let maker_state = User::try_from_slice(&maker_user.data).unwrap();
+    assert_eq!((maker_state.free_balance, maker_state.locked_balance, maker_state.trades_won), (2_000_000_000, 0, 1));
+    assert_eq!(maker.lamports, 11_000_000_000);
+    let events = EVENTS.with(|e| e.borrow().clone());
+    assert!(events.contains(&TradeEvent::PayoutCollected { trade: trade.key, user: maker_user.key, payout: 2_000_000_000 }));
+  }
+
+  #[test]
+  fn test_claim_batch() {
+    let mut fx = Fixture::new();
+    let (mut maker, mut maker_user) = user_accounts(&fx.program_id, 5_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&fx.program_id, 5_000_000_000);
+    let mut expired = trade_account(&fx.program_id, &maker.key, "batch1");
+    let mut running = trade_account(&fx.program_id, &maker.key, "batch2");
+    let mut open = trade_account(&fx.program_id, &maker.key, "batch3");
+    let program_id = fx.program_id;
+    let start = UNIX_NOW.with(|t| t.get());
+
+    // Two Trades Taken 200 seconds apart, a third left Open
+    for (slug, trade) in [("batch1", &mut expired), ("batch2", &mut running), ("batch3", &mut open)] {
+      let accounts = fx.trade_accounts(&mut maker, &mut maker_user, trade);
+      process_instruction(&program_id, &accounts, &make_ix(slug, Direction::Long)).unwrap();
+      drop(accounts);
+      if slug == "batch3" {
+        continue;
+      }
+      let accounts = fx.trade_accounts(&mut taker, &mut taker_user, trade);
+      process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+      drop(accounts);
+      UNIX_NOW.with(|t| t.set(t.get() + 200));
+    }
+    let mut forged = TestAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), expired.lamports, Trade::LEN, false);
+    forged.data.copy_from_slice(&expired.data);
+
+    // No Trades passed
+    let accounts = vec![
+      taker.info(), fx.system.info(), fx.feed.info(), fx.chainlink.info(), fx.observations.info(), fx.config.info(),
+    ];
+    let claim_batch = |round_id: u32| ix_data(20, &ClaimBatch { round_id });
+    assert_eq!(process_instruction(&program_id, &accounts, &claim_batch(0)), Err(ProgramError::NotEnoughAccountKeys));
+    drop(accounts);
+
+    // Observed round just before the end
+    UNIX_NOW.with(|t| t.set(start + 5 * 60));
+    let observation = Observation { unix_timestamp: start as u32 + 5 * 60 - 1, price: 3_100_000_000, round_id: 7 };
+    let mut observations_state = try_from_slice_unchecked::<Observations>(&fx.observations.data).unwrap();
+    observations_state.record(observation).unwrap();
+    observations_state.serialize(&mut &mut fx.observations.data[..]).unwrap();
+
+    // Guard: Round neither live nor observed
+    let accounts = vec![
+      taker.info(), fx.system.info(), fx.feed.info(), fx.chainlink.info(), fx.observations.info(), expired.info(),
+      fx.config.info(),
+    ];
+    assert_eq!(process_instruction(&program_id, &accounts, &claim_batch(8)), Err(TradeError::RoundNotFound.into()));
+
+    // Guard: Round before the end skips the Trade
+    EVENTS.with(|e| e.borrow_mut().clear());
+    process_instruction(&program_id, &accounts, &claim_batch(7)).unwrap();
+    drop(accounts);
+    assert_eq!(EVENTS.with(|e| e.borrow().clone()), vec![TradeEvent::BatchClaimResult {
+      trade: expired.key,
+      error: u64::from(ProgramError::from(TradeError::RoundBeforeTradeEnd)),
+    }]);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&expired.data).unwrap().order_status, OrderStatus::InPlay);
+
+    // The live round settles the expired Trade, the others are skipped with their error
+    EVENTS.with(|e| e.borrow_mut().clear());
+    FEED_PRICE.with(|p| p.set(p.get() + 1));
+    let accounts = vec![
+      taker.info(), fx.system.info(), fx.feed.info(), fx.chainlink.info(), fx.observations.info(), expired.info(),
+      running.info(), open.info(), forged.info(), fx.config.info(),
+    ];
+    let live_round = (start as u32 + 5 * 60) / 10;
+    process_instruction(&program_id, &accounts, &claim_batch(live_round)).unwrap();
+    drop(accounts);
+    FEED_PRICE.with(|p| p.set(3_000_000_000));
+
+    let events = EVENTS.with(|e| e.borrow().clone());
+    let results: Vec<(Pubkey, u64)> = events.iter().filter_map(|event| match event {
+      TradeEvent::BatchClaimResult { trade, error } => Some((*trade, *error)),
+      _ => None,
+    }).collect();
+    assert_eq!(results, vec![
+      (expired.key, 0),
+      (running.key, u64::from(ProgramError::from(TradeError::InvalidTimeForClaim))),
+      (open.key, u64::from(ProgramError::from(TradeError::InvalidTradeForClaim))),
+      (forged.key, u64::from(ProgramError::IncorrectProgramId)),
+    ]);
+    let expired_state = try_from_slice_unchecked::<Trade>(&expired.data).unwrap();
+    assert_eq!((expired_state.order_status, expired_state.closing_price), (OrderStatus::Settled, 3_000_000_001));
+    assert_eq!(try_from_slice_unchecked::<Trade>(&running.data).unwrap().order_status, OrderStatus::InPlay);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&open.data).unwrap().order_status, OrderStatus::Open);
+
+    // Rounds observed after the end, the later one better priced for the Taker
+    UNIX_NOW.with(|t| t.set(start + 10 * 60));
+    let mut observations_state = try_from_slice_unchecked::<Observations>(&fx.observations.data).unwrap();
+    for (seconds, price, round_id) in [(9 * 60, 3_200_000_000, 9), (9 * 60 + 5, 2_500_000_000, 10)] {
+      observations_state.record(Observation { unix_timestamp: start as u32 + seconds, price, round_id }).unwrap();
+    }
+    observations_state.serialize(&mut &mut fx.observations.data[..]).unwrap();
+    let skipped = |error: TradeError| u64::from(ProgramError::from(error));
+    let mut batch_result = |running: &mut TestAccount, round_id: u32| {
+      EVENTS.with(|e| e.borrow_mut().clear());
+      let accounts = vec![
+        taker.info(), fx.system.info(), fx.feed.info(), fx.chainlink.info(), fx.observations.info(), running.info(),
+        fx.config.info(),
+      ];
+      process_instruction(&program_id, &accounts, &claim_batch(round_id)).unwrap();
+      match EVENTS.with(|e| e.borrow().last().cloned()) {
+        Some(TradeEvent::BatchClaimResult { error, .. }) => error,
+        event => panic!("Unexpected event {:?}", event),
+      }
+    };
+
+    // Guard: Trades priced by Observations are claimed alone
+    let mut running_state = try_from_slice_unchecked::<Trade>(&running.data).unwrap();
+    running_state.max_observation_age = 60;
+    running_state.serialize(&mut &mut running.data[..]).unwrap();
+    assert_eq!(batch_result(&mut running, 9), skipped(TradeError::TradeNotPricedByRound));
+    running_state.max_observation_age = 0;
+    running_state.serialize(&mut &mut running.data[..]).unwrap();
+
+    // Guard: Only the first Round after the end, within MAX_ROUND_DELAY of it
+    assert_eq!(batch_result(&mut running, 10), skipped(TradeError::RoundTooLate));
+    assert_eq!(batch_result(&mut running, (start as u32 + 10 * 60) / 10), skipped(TradeError::RoundTooLate));
+
+    // The first observed round after the end settles on its price
+    assert_eq!(batch_result(&mut running, 9), 0);
+    assert_eq!(try_from_slice_unchecked::<Trade>(&running.data).unwrap().closing_price, 3_200_000_000);
+  }
+
+  #[test]
+  fn test_list_market_durations() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let mut payer = TestAccount::new(Pubkey::new_unique(), system_program::id(), 10_000_000_000, 0, true);
+    let (market_pda, _market_bump) = find_market_address(&program_id, "BTC / USD");
+    let mut market = TestAccount::new(market_pda, program_id, Rent::default().minimum_balance(Market::LEN), Market::LEN, false);
+    let (observations_pda, observations_bump) = find_observations_address(&program_id, "BTC / USD");
+    let lamports = Rent::default().minimum_balance(Observations::LEN);
+    let mut observations = TestAccount::new(observations_pda, program_id, lamports, Observations::LEN, false);
+    let list = |min_duration: u32, max_duration: u32, expiry_interval: u32, expiry_offset: u32, twap_window: u32| {
+      ix_data(11, &ListMarket {
+        symbol: String::from("BTC / USD"),
+        min_duration,
+        max_duration,
+        expiry_interval,
+        expiry_offset,
+        tie_outcome: Outcome::Void,
+        late_outcome: Outcome::Void,
+        claim_window: 0,
+        dust_recipient: Party::Maker,
+        challenge_window: 0,
+        twap_window,
+        max_observation_age: 0,
+      })
+    };
+
+    // Bounds must be ordered and non zero, the offset inside the interval, the TWAP window inside the shortest Trade
+    let accounts = vec![fx.admin.info(), fx.config.info(), market.info(), observations.info(), fx.system.info(), payer.info()];
+    for invalid in [list(0, 60, 0, 0, 0), list(120, 60, 0, 0, 0), list(60, 3600, 3600, 3600, 0), list(60, 3600, 0, 0, 61)] {
+      assert_eq!(process_instruction(&program_id, &accounts, &invalid), Err(TradeError::InvalidMarketDurations.into()));
+    }
+
+    // Symbol longer than a PDA seed
+    let mut long_symbol = ListMarket::try_from_slice(&list(60, 3600, 0, 0, 0)[1..]).unwrap();
+    long_symbol.symbol = "S".repeat(33);
+    assert_eq!(process_instruction(&program_id, &accounts, &ix_data(11, &long_symbol)), Err(TradeError::SeedTooLong.into()));
+
+    // Daily Expiry at 16:00 UTC
+    process_instruction(&program_id, &accounts, &list(60, 7 * 86400, 86400, 16 * 3600, 60)).unwrap();
+    drop(accounts);
+    assert_eq!(try_from_slice_unchecked::<Observations>(&observations.data).unwrap().bump, observations_bump);
+    let market_state = try_from_slice_unchecked::<Market>(&market.data).unwrap();
+    assert_eq!(market_state.unix_end(Duration::Expiry(1_650_038_400), 1_650_000_000), Ok(1_650_038_400));
+    assert_eq!(market_state.unix_end(Duration::Expiry(1_650_002_400), 1_650_000_000), Err(TradeError::ExpiryNotOnCalendar));
+    assert_eq!(market_state.max_duration, 7 * 86400);
+  }
+
+  #[test]
+  fn test_account_validation() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let (mut maker, mut maker_user) = user_accounts(&program_id, 2_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&program_id, 2_000_000_000);
+    let mut trade = trade_account(&program_id, &maker.key, "checks1");
+    let make = make_ix("checks1", Direction::Long);
+
+    // Create User: PDA of another Authority, then a fake System Program
+    let (mut authority, mut user) = user_accounts(&program_id, 0);
+    let accounts = vec![authority.info(), maker_user.info(), fx.system.info()];
+    assert_eq!(process_instruction(&program_id, &accounts, &[0]), Err(TradeError::InvalidUserAccount.into()));
+    drop(accounts);
+    let mut fake_system = TestAccount::new(Pubkey::new_unique(), Pubkey::default(), 0, 0, false);
+    let accounts = vec![authority.info(), user.info(), fake_system.info()];
+    assert_eq!(process_instruction(&program_id, &accounts, &[0]), Err(ProgramError::IncorrectProgramId));
+    drop(accounts);
+
+    // Deposit: read-only User Account
+    user.is_writable = false;
+    let accounts = vec![authority.info(), user.info(), fx.system.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &ix_data(5, &Deposit { amount: 1 })),
+      Err(TradeError::AccountNotWritable.into())
+    );
+    drop(accounts);
+
+    // Make: unsigned, read-only Trade, fake Chainlink Program and a Feed it does not own
+    maker.is_signer = false;
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &make), Err(ProgramError::MissingRequiredSignature));
+    drop(accounts);
+    maker.is_signer = true;
+    trade.is_writable = false;
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &make), Err(TradeError::AccountNotWritable.into()));
+    drop(accounts);
+    trade.is_writable = true;
+    fx.chainlink.key = Pubkey::new_unique();
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &make), Err(ProgramError::IncorrectProgramId));
+    drop(accounts);
+    fx.chainlink.key = chainlink_solana::ID;
+    fx.feed.owner = Pubkey::new_unique();
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(process_instruction(&program_id, &accounts, &make), Err(ProgramError::IncorrectProgramId));
+    drop(accounts);
+    fx.feed.owner = chainlink_solana::ID;
+
+    // Make: Slug longer than a PDA seed
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &make_ix(&"s".repeat(33), Direction::Long)),
+      Err(TradeError::SeedTooLong.into())
+    );
+    drop(accounts);
+
+    // Make and Take
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make).unwrap();
+    drop(accounts);
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).unwrap();
+    drop(accounts);
+
+    // Claim: Trade copied to another Program Account
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    let mut copied = TestAccount::new(Pubkey::new_unique(), program_id, trade.lamports, Trade::LEN, false);
+    copied.data.copy_from_slice(&trade.data);
+    let accounts = fx.claim_accounts(&mut taker, &mut copied);
+    assert_eq!(process_instruction(&program_id, &accounts, &[4]), Err(TradeError::InvalidTradeAccount.into()));
+    drop(accounts);
+
+    // Claim with every Account valid
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+
+    // Collect: read-only Trade, Trade not owned by the Program, then another Authority's User
+    trade.is_writable = false;
+    assert_eq!(fx.collect(&mut maker, &mut maker_user, &mut trade), Err(TradeError::AccountNotWritable.into()));
+    trade.is_writable = true;
+    trade.owner = Pubkey::new_unique();
+    assert_eq!(fx.collect(&mut maker, &mut maker_user, &mut trade), Err(ProgramError::IncorrectProgramId));
+    trade.owner = program_id;
+    assert_eq!(fx.collect(&mut maker, &mut taker_user, &mut trade), Err(TradeError::InvalidUserAccount.into()));
+    fx.collect(&mut maker, &mut maker_user, &mut trade).unwrap();
+  }
+
+  #[test]
+  fn test_deposit() {
+    let mut fx = Fixture::new();
+    let (mut authority, mut user) = user_accounts(&fx.program_id, 0);
+
+    // Deposit 1 SOL
+    let accounts = vec![authority.info(), user.info(), fx.system.info()];
+    process_instruction(&fx.program_id, &accounts, &ix_data(5, &Deposit { amount: 1_000_000_000 })).unwrap();
+
+    // Zero Deposit Rejected
+    assert!(process_instruction(&fx.program_id, &accounts, &ix_data(5, &Deposit { amount: 0 })).is_err());
+    drop(accounts);
+
+    let user_state = User::try_from_slice(&user.data).unwrap();
+    assert_eq!(user_state.deposited, 1_000_000_000);
+    assert_eq!(user_state.free_balance, 1_000_000_000);
+    assert_eq!(user.lamports, Rent::default().minimum_balance(User::LEN) + 1_000_000_000);
+    assert_eq!(authority.lamports, 9_000_000_000);
+  }
+
+  #[test]
+  fn test_withdraw_free_balance_only() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let (mut authority, mut user) = user_accounts(&program_id, 1_000_000_000);
+
+    // Lock half of the balance as if staked on a trade
+    let mut user_state = User::try_from_slice(&user.data).unwrap();
+    user_state.free_balance = 500_000_000;
+    user_state.locked_balance = 500_000_000;
+    user_state.serialize(&mut &mut user.data[..]).unwrap();
+
+    // Partial Withdraw, then reject dipping into Locked
+    let accounts = fx.user_accounts(&mut authority, &mut user);
+    process_instruction(&program_id, &accounts, &ix_data(6, &Withdraw { amount: 200_000_000 })).unwrap();
+    assert!(process_instruction(&program_id, &accounts, &ix_data(6, &Withdraw { amount: 300_000_001 })).is_err());
+
+    // Sweep remaining Free Balance
+    process_instruction(&program_id, &accounts, &[1]).unwrap();
+    drop(accounts);
+
+    let user_state = User::try_from_slice(&user.data).unwrap();
+    assert_eq!(user_state.free_balance, 0);
+    assert_eq!(user_state.locked_balance, 500_000_000);
+    assert_eq!(authority.lamports, 10_500_000_000);
+  }
+
+  #[test]
+  fn test_balance_boundaries() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let rent_lamports = Rent::default().minimum_balance(User::LEN);
+    let (mut authority, mut user) = user_accounts(&program_id, 1_000_000_000);
+
+    // Withdraw the exact Free Balance down to rent, then nothing more
+    let accounts = fx.user_accounts(&mut authority, &mut user);
+    process_instruction(&program_id, &accounts, &ix_data(6, &Withdraw { amount: 1_000_000_000 })).unwrap();
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &ix_data(6, &Withdraw { amount: 1 })),
+      Err(TradeError::InsufficientFreeBalance.into())
+    );
+    drop(accounts);
+    assert_eq!(user.lamports, rent_lamports);
+
+    // Below rent nothing can be swept
+    user.lamports = rent_lamports - 1;
+    let accounts = fx.user_accounts(&mut authority, &mut user);
+    assert_eq!(process_instruction(&program_id, &accounts, &[1]), Err(TradeError::NotEnoughLamports.into()));
+    drop(accounts);
+
+    // Free Balance at u64::MAX cannot take a Deposit
+    let mut user_state = User::try_from_slice(&user.data).unwrap();
+    user_state.free_balance = u64::MAX;
+    user_state.serialize(&mut &mut user.data[..]).unwrap();
+    let accounts = vec![authority.info(), user.info(), fx.system.info()];
+    assert_eq!(
+      process_instruction(&program_id, &accounts, &ix_data(5, &Deposit { amount: 1 })),
+      Err(TradeError::MathOverflow.into())
+    );
+  }
+
+  #[test]
+  fn test_trade_boundaries() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let trade_rent = Rent::default().minimum_balance(Trade::LEN);
+    let (mut maker, mut maker_user) = user_accounts(&program_id, 1_000_000_000);
+    let (mut taker, mut taker_user) = user_accounts(&program_id, 1_000_000_000);
+    let mut trade = trade_account(&program_id, &maker.key, "edge1");
+
+    // Stake the exact Free Balance
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("edge1", Direction::Long)).unwrap();
+    drop(accounts);
+
+    // Trade end past u32 Unix time
+    UNIX_NOW.with(|t| t.set(u32::MAX as i64 - 60));
+    let accounts = fx.trade_accounts(&mut taker, &mut taker_user, &mut trade);
+    let take = take_ix(Direction::Short);
+    assert_eq!(process_instruction(&program_id, &accounts, &take), Err(TradeError::MathOverflow.into()));
+
+    // Clock past u32 Unix time
+    UNIX_NOW.with(|t| t.set(u32::MAX as i64 + 1));
+    assert_eq!(process_instruction(&program_id, &accounts, &take), Err(TradeError::MathOverflow.into()));
+
+    // Take with the exact Free Balance
+    UNIX_NOW.with(|t| t.set(1_650_000_000));
+    process_instruction(&program_id, &accounts, &take).unwrap();
+    drop(accounts);
+    assert_eq!(User::try_from_slice(&taker_user.data).unwrap().free_balance, 0);
+
+    // Claim a Void tie with an odd lamport sent to the Trade Account (dust to the Maker)
+    UNIX_NOW.with(|t| t.set(t.get() + 5 * 60));
+    trade.lamports += 1;
+    let accounts = fx.claim_accounts(&mut taker, &mut trade);
+    process_instruction(&program_id, &accounts, &[4]).unwrap();
+    drop(accounts);
+    fx.collect(&mut maker, &mut maker_user, &mut trade).unwrap();
+    fx.collect(&mut taker, &mut taker_user, &mut trade).unwrap();
+    assert_eq!(trade.lamports, trade_rent);
+    let maker_state = User::try_from_slice(&maker_user.data).unwrap();
+    assert_eq!(maker_state.free_balance, 1_000_000_001);
+    assert_eq!(maker_state.locked_balance, 0);
+    assert_eq!(maker_state.realized_pnl, 1);
+    assert_eq!(User::try_from_slice(&taker_user.data).unwrap().free_balance, 1_000_000_000);
+  }
+
+  #[test]
+  fn test_close_user() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let (mut authority, mut user) = user_accounts(&program_id, 1_000_000_000);
+    let user_lamports = user.lamports;
+
+    // Reject Close with an InPlay trade
+    let mut user_state = User::try_from_slice(&user.data).unwrap();
+    user_state.open_positions = 1;
+    user_state.serialize(&mut &mut user.data[..]).unwrap();
+    let accounts = fx.user_accounts(&mut authority, &mut user);
+    assert!(process_instruction(&program_id, &accounts, &[7]).is_err());
+    drop(accounts);
+
+    // Close once settled
+    user_state.open_positions = 0;
+    user_state.serialize(&mut &mut user.data[..]).unwrap();
+    let accounts = fx.user_accounts(&mut authority, &mut user);
+    process_instruction(&program_id, &accounts, &[7]).unwrap();
+    drop(accounts);
+
+    assert_eq!(user.lamports, 0);
+    assert_eq!(authority.lamports, 10_000_000_000 + user_lamports);
+    assert!(user.data.iter().all(|b| *b == 0));
+  }
+
+  #[test]
+  fn test_session_key() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let (mut authority, mut user) = user_accounts(&program_id, 2_000_000_000);
+    let mut session = TestAccount::new(Pubkey::new_unique(), system_program::id(), 100_000_000, 0, true);
+
+    // Register Session Key for Make only
+    let register = SessionKey {
+      session_key: session.key,
+      expiry: UNIX_NOW.with(|t| t.get()) as u32 + 60,
+      scope: SESSION_SCOPE_MAKE,
+    };
+    let accounts = vec![authority.info(), user.info()];
+    process_instruction(&program_id, &accounts, &ix_data(8, &register)).unwrap();
+    drop(accounts);
+
+    // Session Key Makes on behalf of Authority (Trade PDA still seeded by Authority)
+    let mut trade = trade_account(&program_id, &authority.key, "session1");
+    let accounts = fx.trade_accounts(&mut session, &mut user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("session1", Direction::Long)).unwrap();
+
+    // Session Key cannot Take (out of scope) or Withdraw
+    assert!(process_instruction(&program_id, &accounts, &take_ix(Direction::Short)).is_err());
+    let withdraw_accounts = vec![accounts[0].clone(), accounts[1].clone(), accounts[7].clone()];
+    assert!(process_instruction(&program_id, &withdraw_accounts, &ix_data(6, &Withdraw { amount: 1 })).is_err());
+    drop(withdraw_accounts);
+
+    // Expired Session Key rejected
+    UNIX_NOW.with(|t| t.set(t.get() + 60));
+    assert!(process_instruction(&program_id, &accounts, &make_ix("session2", Direction::Long)).is_err());
+    drop(accounts);
+
+    let user_state = User::try_from_slice(&user.data).unwrap();
+    assert_eq!(user_state.trades_made, 1);
+    assert_eq!(user_state.locked_balance, 1_000_000_000);
+  }
+
+  #[test]
+  fn test_initialize_config() {
+    let mut fx = Fixture::new();
+    let rent = Rent::default();
+    let mut config = TestAccount::new(fx.config.key, fx.program_id, rent.minimum_balance(Config::LEN), Config::LEN, false);
+    let mut stranger = TestAccount::new(Pubkey::new_unique(), system_program::id(), 10_000_000_000, 0, true);
+
+    // ProgramData of the Upgradeable Loader (None = immutable Program)
+    let program_data = |key: Pubkey, owner: Pubkey, upgrade_authority_address: Option<Pubkey>| {
+      let mut account = TestAccount::new(key, owner, 0, 0, false);
+      account.data = bincode::serialize(&UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address }).unwrap();
+      account
+    };
+    let (program_data_pda, _) = find_program_data_address(&fx.program_id);
+    let mut upgradeable = program_data(program_data_pda, bpf_loader_upgradeable::id(), Some(fx.admin.key));
+    let mut immutable = program_data(program_data_pda, bpf_loader_upgradeable::id(), None);
+    let mut foreign = program_data(program_data_pda, system_program::id(), Some(fx.admin.key));
+    let other_program = find_program_data_address(&Pubkey::new_unique()).0;
+    let mut other = program_data(other_program, bpf_loader_upgradeable::id(), Some(fx.admin.key));
+
+    let mut init = |signer: &mut TestAccount, program_data: &mut TestAccount| {
+      let accounts = vec![signer.info(), config.info(), fx.system.info(), program_data.info()];
+      process_instruction(&fx.program_id, &accounts, &[9])
+    };
+
+    // Guard: First caller cannot front-run the Upgrade Authority
+    assert_eq!(init(&mut stranger, &mut upgradeable), Err(TradeError::NotUpgradeAuthority.into()));
+    assert_eq!(init(&mut stranger, &mut immutable), Err(TradeError::NotUpgradeAuthority.into()));
+
+    // Guard: ProgramData must be this Program's, held by the Upgradeable Loader
+    let mut admin = TestAccount::new(fx.admin.key, system_program::id(), 10_000_000_000, 0, true);
+    assert_eq!(init(&mut admin, &mut foreign), Err(ProgramError::IncorrectProgramId));
+    assert_eq!(init(&mut admin, &mut other), Err(TradeError::InvalidProgramDataAccount.into()));
+
+    assert_eq!(init(&mut admin, &mut upgradeable), Ok(()));
+    assert_eq!(Config::try_from_slice(&config.data).unwrap().admin, fx.admin.key);
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn test_pause_and_market_halt() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let (mut maker, mut maker_user) = user_accounts(&program_id, 2_000_000_000);
+    let mut trade = trade_account(&program_id, &maker.key, "pause1");
+
+    // Open Order placed before the Pause
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    process_instruction(&program_id, &accounts, &make_ix("pause1", Direction::Long)).unwrap();
+    drop(accounts);
+
+    // Only Admin can Pause
+    let mut accounts = fx.admin_accounts();
+    accounts[0] = maker.info();
+    assert!(process_instruction(&program_id, &accounts, &ix_data(10, &Pause { flags: PAUSE_MAKE })).is_err());
+    drop(accounts);
+    let accounts = fx.admin_accounts();
+    process_instruction(&program_id, &accounts, &ix_data(10, &Pause { flags: PAUSE_MAKE })).unwrap();
+    drop(accounts);
+
+    // Make Paused but Cancel still allowed
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert!(process_instruction(&program_id, &accounts, &make_ix("pause2", Direction::Long)).is_err());
+    process_instruction(&program_id, &accounts[..3], &[13]).unwrap();
+    drop(accounts);
+
+    // Resume and Halt Market
+    let accounts = fx.admin_accounts();
+    process_instruction(&program_id, &accounts, &ix_data(10, &Pause { flags: 0 })).unwrap();
+    process_instruction(&program_id, &accounts, &ix_data(12, &MarketHalt { halted: true })).unwrap();
+    drop(accounts);
+    let mut trade = trade_account(&program_id, &maker.key, "pause2");
+    let accounts = fx.trade_accounts(&mut maker, &mut maker_user, &mut trade);
+    assert!(process_instruction(&program_id, &accounts, &make_ix("pause2", Direction::Long)).is_err());
+    drop(accounts);
+
+    let user_state = User::try_from_slice(&maker_user.data).unwrap();
+    assert_eq!(user_state.free_balance, 2_000_000_000);
+    assert_eq!(user_state.open_positions, 0);
+  }
+
+  #[test]
+  fn test_admin_transfer_to_multisig() {
+    let mut fx = Fixture::new();
+    let program_id = fx.program_id;
+    let mut signers: Vec<TestAccount> = (0..3)
+      .map(|_| TestAccount::new(Pubkey::new_unique(), system_program::id(), 0, 0, true))
+      .collect();
+
+    // Create 2 of 3 Multisig
+    let (multisig_pda, _multisig_bump) = find_multisig_address(&program_id, &fx.admin.key);
+    let mut multisig = TestAccount::new(multisig_pda, program_id, 0, Multisig::LEN, false);
+    let create = CreateMultisig { m: 2, signers: signers.iter().map(|a| a.key).collect() };
+    let accounts = vec![fx.admin.info(), multisig.info(), fx.system.info()];
+    assert!(process_instruction(&program_id, &accounts, &ix_data(16, &CreateMultisig { m: 4, signers: create.signers.clone() })).is_err());
+    process_instruction(&program_id, &accounts, &ix_data(16, &create)).unwrap();
+    drop(accounts);
+
+    // Propose Multisig as Admin (old Admin still in charge until accepted)
+    let accounts = vec![fx.admin.info(), fx.config.info()];
+    process_instruction(&program_id, &accounts, &ix_data(14, &ProposeAdmin { new_admin: multisig_pda })).unwrap();
+    drop(accounts);
+
+    // Accept needs 2 of 3 Signers
+    let (first, rest) = signers.split_at_mut(1);
+    let mut accounts = vec![multisig.info(), fx.config.info(), first[0].info()];
+    assert!(process_instruction(&program_id, &accounts, &[15]).is_err());
+    accounts.push(rest[1].info());
+    process_instruction(&program_id, &accounts, &[15]).unwrap();
+
+    // Multisig now Admin: Pause with 2 Signers, old Admin rejected
+    let pause = ix_data(10, &Pause { flags: PAUSE_MAKE });
+    process_instruction(&program_id, &accounts, &pause).unwrap();
+    drop(accounts);
+    let accounts = vec![fx.admin.info(), fx.config.info()];
+    assert!(process_instruction(&program_id, &accounts, &pause).is_err());
+    drop(accounts);
+
+    let config_state = Config::try_from_slice(&fx.config.data).unwrap();
+    assert_eq!(config_state.admin, multisig_pda);
+    assert_eq!(config_state.pending_admin, Pubkey::default());
+    assert_eq!(config_state.pause_flags, PAUSE_MAKE);
+  }
+}
@@ -4,7 +4,7 @@ use solana_program::{
 use thiserror::Error;
 
 // Custom Error Enum
-#[derive(Error, Debug, Copy, Clone)]
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum TradeError {
     #[error("Invalid Instruction")]
     InvalidInstruction,
@@ -44,11 +44,143 @@ pub enum TradeError {
 
     #[error("Account not Writable")]
     AccountNotWritable,
+
+    #[error("Invalid Amount")]
+    InvalidAmount,
+
+    #[error("Not Enough Free Balance (Lamports Locked in Trades)")]
+    InsufficientFreeBalance,
+
+    #[error("User Account has Open or InPlay Trades")]
+    OpenPositionsExist,
+
+    #[error("Session Key Not Valid (Unknown, Expired or Out of Scope)")]
+    InvalidSessionKey,
+
+    #[error("Session Scope Not Allowed")]
+    InvalidSessionScope,
+
+    #[error("Invalid Config Account")]
+    InvalidConfigAccount,
+
+    #[error("Invalid Market Account")]
+    InvalidMarketAccount,
+
+    #[error("Signer is Not the Admin")]
+    NotAdmin,
+
+    #[error("Instruction Paused by Admin")]
+    Paused,
+
+    #[error("Market Halted by Admin")]
+    MarketHalted,
+
+    #[error("Not a Valid Trade To Cancel")]
+    InvalidTradeForCancel,
+
+    #[error("No Pending Admin or Signer is Not the Pending Admin")]
+    NotPendingAdmin,
+
+    #[error("Invalid Multisig Configuration")]
+    InvalidMultisig,
+
+    #[error("Not Enough Multisig Signers")]
+    NotEnoughMultisigSigners,
+
+    #[error("Invalid Order Status Transition")]
+    InvalidStatusTransition,
+
+    #[error("Unknown Instruction Tag")]
+    UnknownInstruction,
+
+    #[error("Instruction Payload Truncated")]
+    TruncatedInstruction,
+
+    #[error("Trailing Bytes after Instruction Payload")]
+    TrailingInstructionBytes,
+
+    #[error("Invalid Enum or Field Value in Instruction Payload")]
+    InvalidInstructionValue,
+
+    #[error("Arithmetic Overflow or Underflow")]
+    MathOverflow,
+
+    #[error("Taker Direction must be opposite of Maker Direction")]
+    InvalidTakerDirection,
+
+    #[error("Maker cannot Take own Trade")]
+    SelfTrade,
+
+    #[error("Taker not allowed for this Trade")]
+    TakerNotAllowed,
+
+    #[error("Benchmark Price outside Taker Bounds")]
+    TakerPriceOutOfBounds,
+
+    #[error("Benchmark Price outside Maker Reference Deviation")]
+    MakerPriceOutOfBounds,
+
+    #[error("Invalid Price Protection")]
+    InvalidPriceProtection,
+
+    #[error("Duration outside Market Bounds")]
+    DurationOutOfBounds,
+
+    #[error("Expiry not on Market Calendar")]
+    ExpiryNotOnCalendar,
+
+    #[error("Invalid Market Duration Bounds or Calendar")]
+    InvalidMarketDurations,
+
+    #[error("Signer is not the Admin or Resolver")]
+    NotResolver,
+
+    #[error("Challenge Window still Open")]
+    ChallengeWindowOpen,
+
+    #[error("Challenge Window Closed")]
+    ChallengeWindowClosed,
+
+    #[error("Invalid Resolution or Reason")]
+    InvalidResolution,
+
+    #[error("Invalid Observations Account")]
+    InvalidObservationsAccount,
+
+    #[error("Observation not newer than the latest")]
+    StaleObservation,
+
+    #[error("No Observations in the TWAP Window")]
+    NoObservations,
+
+    #[error("No uncollected Payout for this User")]
+    NothingToCollect,
+
+    #[error("Slug or Symbol longer than a PDA Seed")]
+    SeedTooLong,
+
+    #[error("Invalid ProgramData Account")]
+    InvalidProgramDataAccount,
+
+    #[error("Signer is not the Program's Upgrade Authority")]
+    NotUpgradeAuthority,
+
+    #[error("Chainlink Round neither live nor observed")]
+    RoundNotFound,
+
+    #[error("Chainlink Round before the Trade end")]
+    RoundBeforeTradeEnd,
+
+    #[error("Chainlink Round not the first after the Trade end")]
+    RoundTooLate,
+
+    #[error("Trade settles on Observations, not a single Round")]
+    TradeNotPricedByRound,
 }
 
 // Custom Error Function
 impl From<TradeError> for ProgramError {
     fn from(e: TradeError) -> Self {
-        return ProgramError::Custom(e as u32);
+        ProgramError::Custom(e as u32)
     }
 }
\ No newline at end of file
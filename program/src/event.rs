@@ -0,0 +1,178 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+use crate::state::{ContractSize, Direction, Duration};
+
+
+// Log Prefix written by the runtime for sol_log_data
+pub const LOG_PREFIX: &str = "Program data: ";
+
+// Trade Event
+// Borsh encoded and logged as base64 on every state transition
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum TradeEvent {
+  UserCreated {
+    user: Pubkey,
+    authority: Pubkey,
+  },
+  Deposited {
+    user: Pubkey,
+    amount: u64,
+    deposited: u64,
+    free_balance: u64,
+  },
+  Withdrawn {
+    user: Pubkey,
+    amount: u64,
+    free_balance: u64,
+  },
+  UserClosed {
+    user: Pubkey,
+    lamports: u64,
+  },
+  SessionKeyRegistered {
+    user: Pubkey,
+    session_key: Pubkey,
+    expiry: u32,
+    scope: u8,
+  },
+  TradeMade {
+    trade: Pubkey,
+    maker: Pubkey,
+    symbol: String,
+    contract_size: ContractSize,
+    direction: Direction,
+    duration: Duration,
+    stake: u64,
+    unix_created: u32,
+    allowed_taker: Pubkey, // Pubkey::default() = anyone
+  },
+  TradeTaken {
+    trade: Pubkey,
+    taker: Pubkey,
+    stake: u64,
+    benchmark_price: i128,
+    unix_start: u32,
+    unix_end: u32,
+  },
+  TradeSettled {
+    trade: Pubkey,
+    winner: u8, // 0 = Maker, 1 = Taker, 2 = Void (Stakes refunded)
+    closing_price: i128,
+    payout: u64,
+  },
+  TradeCancelled {
+    trade: Pubkey,
+    maker: Pubkey,
+    refund: u64,
+  },
+  ConfigInitialized {
+    admin: Pubkey,
+  },
+  PauseSet {
+    pause_flags: u8,
+  },
+  MarketListed {
+    market: Pubkey,
+    symbol: String,
+  },
+  MarketHaltSet {
+    market: Pubkey,
+    halted: bool,
+  },
+  AdminProposed {
+    pending_admin: Pubkey,
+  },
+  AdminAccepted {
+    admin: Pubkey,
+  },
+  MultisigCreated {
+    multisig: Pubkey,
+    m: u8,
+    signers: Vec<Pubkey>,
+  },
+  ResolverSet {
+    resolver: Pubkey,
+  },
+  ObservationRecorded {
+    market: Pubkey,
+    unix_timestamp: u32,
+    price: i128,
+    round_id: u32,
+  },
+  PayoutCollected {
+    trade: Pubkey,
+    user: Pubkey,
+    payout: u64,
+  },
+  BatchClaimResult {
+    trade: Pubkey,
+    error: u64, // 0 when Claimed, else the ProgramError code the Trade was skipped with
+  },
+  TradeClaimed {
+    trade: Pubkey,
+    closing_price: i128,
+    unix_final: u32, // Payout from this time unless Resolved
+  },
+  TradeResolved {
+    trade: Pubkey,
+    resolver: Pubkey,
+    voided: bool,
+    closing_price: i128, // Replacement Closing Price (0 when voided)
+    reason: String,
+  },
+}
+
+impl TradeEvent {
+
+  // Emit Event to Program Logs
+  pub fn emit(&self) -> ProgramResult {
+    sol_log_data(&[&self.try_to_vec()?]);
+    Ok(())
+  }
+
+  // Decode Event from a single Program Log line
+  // Returns None for free-text msg! lines or data not produced by this Program
+  pub fn decode_log(line: &str) -> Option<Self> {
+    let encoded = line.strip_prefix(LOG_PREFIX)?;
+    let data = base64::decode(encoded).ok()?;
+    Self::try_from_slice(&data).ok()
+  }
+
+  // Decode all Events from a Transaction's Program Logs
+  pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<Self> {
+    logs.iter().filter_map(|line| Self::decode_log(line.as_ref())).collect()
+  }
+}
+
+
+// TEST MODULE
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_decode_logs() {
+    let event = TradeEvent::TradeSettled {
+      trade: Pubkey::new_unique(),
+      winner: 2,
+      closing_price: 3_000_000_000,
+      payout: 2_000_000_000,
+    };
+    let logs = vec![
+      String::from("Program log: Lamports to Transfer: 2000000000"),
+      format!("{}{}", LOG_PREFIX, base64::encode(event.try_to_vec().unwrap())),
+      format!("{}{}", LOG_PREFIX, base64::encode([255u8])),
+    ];
+    assert_eq!(TradeEvent::decode_logs(&logs), vec![event]);
+  }
+
+  #[test]
+  fn test_discriminants_stable() {
+
+    // New Events are appended so existing indexers keep decoding earlier variants
+    let cancelled = TradeEvent::TradeCancelled { trade: Pubkey::default(), maker: Pubkey::default(), refund: 0 };
+    assert_eq!(cancelled.try_to_vec().unwrap()[0], 8);
+    let multisig = TradeEvent::MultisigCreated { multisig: Pubkey::default(), m: 1, signers: vec![] };
+    assert_eq!(multisig.try_to_vec().unwrap()[0], 15);
+  }
+}
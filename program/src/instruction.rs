@@ -1,6 +1,11 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{program_error::ProgramError, msg};
+use solana_program::{program_error::ProgramError, msg, pubkey::Pubkey};
 use crate::error::TradeError;
+use crate::state::{ContractSize, Direction, Duration, Outcome, Party};
+use std::fmt::Debug;
+
+// A Transaction Packet bounds every Instruction, so no Payload needs more bytes
+const MAX_PAYLOAD_LEN: usize = 1232;
 
 
 // MAKE
@@ -9,16 +14,119 @@ use crate::error::TradeError;
 pub struct Make {
   pub symbol: String,
   pub slug: String, // For unique Trade Account creation
-  pub contract_size: u8, // 0 = 0.1 Sol, 1 = 1 Sol and 2 = 5 Sol
-  pub direction: u8, // 0 = Long, 1 = Short
-  pub duration: u8, // 0 = 5Min, 1 = 1 Hour, 2 = 1 Day
+  pub contract_size: ContractSize, // 0 = 0.1 Sol, 1 = 1 Sol and 2 = 5 Sol
+  pub direction: Direction, // 0 = Long, 1 = Short
+  pub duration: Duration, // 0 = 5Min, 1 = 1 Hour, 2 = 1 Day, 3 = Seconds, 4 = Expiry
+  pub allowed_taker: Pubkey, // Only this Taker Authority may Take (OTC), Pubkey::default() = anyone
+  pub reference_price: i128, // Maker's price in Chainlink units (0 = no price protection)
+  pub max_deviation_bps: u16, // Fill only if the Benchmark is within this many bps of the Reference Price
 }
 
 // TAKE
 // Take Instruction
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct Take {
-  pub direction: u8,
+  pub direction: Direction, // Must be opposite of the Maker Direction
+  pub min_price: i128, // Lowest acceptable Benchmark Price in Chainlink units (0 = no bound)
+  pub max_price: i128, // Highest acceptable Benchmark Price in Chainlink units (0 = no bound)
+}
+
+// DEPOSIT
+// Deposit Instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Deposit {
+  pub amount: u64, // Lamports moved from Authority Wallet to User Account
+}
+
+// WITHDRAW
+// Withdraw Instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Withdraw {
+  pub amount: u64, // Lamports moved from User Account Free Balance to Authority Wallet
+}
+
+// SESSION KEY
+// Register Session Key Instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SessionKey {
+  pub session_key: Pubkey, // Delegate (Pubkey::default() to revoke)
+  pub expiry: u32, // Unix time
+  pub scope: u8, // Bit flags of SESSION_SCOPE_* (Make, Take, Cancel)
+}
+
+// PAUSE
+// Set Pause Flags Instruction (Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pause {
+  pub flags: u8, // Bit flags of PAUSE_* (0 to resume)
+}
+
+// LIST MARKET
+// List Market Instruction (Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ListMarket {
+  pub symbol: String, // Must match Chainlink Feed Description
+  pub min_duration: u32, // Shortest Trade in seconds (at least 1)
+  pub max_duration: u32, // Longest Trade in seconds
+  pub expiry_interval: u32, // Calendar Expiries every interval seconds (0 = none)
+  pub expiry_offset: u32, // Less than expiry_interval
+  pub tie_outcome: Outcome, // 0 = Void, 1 = Maker, 2 = Taker
+  pub late_outcome: Outcome, // 0 = Void, 1 = Maker, 2 = Taker
+  pub claim_window: u32, // Seconds after the end before a Claim is late (0 = never)
+  pub dust_recipient: Party, // 0 = Maker, 1 = Taker
+  pub challenge_window: u32, // Seconds to void or correct a Claim before payout (0 = pay at Claim)
+  pub twap_window: u32, // Settle on the average Observation this many seconds before the end (0 = single round)
+  pub max_observation_age: u32, // Claim on Observations at most this old instead of live rounds (0 = live rounds only)
+}
+
+// MARKET HALT
+// Halt or Resume Market Instruction (Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MarketHalt {
+  pub halted: bool,
+}
+
+// PROPOSE ADMIN
+// Propose Admin Instruction (Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ProposeAdmin {
+  pub new_admin: Pubkey, // Wallet or Multisig Account (Pubkey::default() to cancel)
+}
+
+// CREATE MULTISIG
+// Create Multisig Instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CreateMultisig {
+  pub m: u8, // Signatures required
+  pub signers: Vec<Pubkey>, // Up to MAX_MULTISIG_SIGNERS
+}
+
+// SET RESOLVER
+// Set Resolver Instruction (Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SetResolver {
+  pub resolver: Pubkey, // Wallet (Pubkey::default() to remove)
+}
+
+// RESOLVE TRADE
+// Resolve Trade Instruction (Admin or Resolver)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+  Void, // Refund both Stakes
+  ClosingPrice(i128), // Replace the Closing Price (payout after the challenge window)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ResolveTrade {
+  pub resolution: Resolution,
+  pub reason: String, // Stored on the Trade and in the TradeResolved Event (up to MAX_REASON_LEN bytes)
+}
+
+// CLAIM BATCH
+// Claim Batch Instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ClaimBatch {
+  pub round_id: u32, // Chainlink round to settle on: the live round or one recorded in the Observations
 }
 
 // Trade Instruction
@@ -29,40 +137,137 @@ pub enum TradeInstruction {
   MakeTrade(Make),
   TakeTrade(Take),
   Claim,
+  Deposit(Deposit),
+  Withdraw(Withdraw),
+  CloseUser,
+  RegisterSessionKey(SessionKey),
+  InitializeConfig,
+  SetPause(Pause),
+  ListMarket(ListMarket),
+  SetMarketHalt(MarketHalt),
+  CancelTrade,
+  ProposeAdmin(ProposeAdmin),
+  AcceptAdmin,
+  CreateMultisig(CreateMultisig),
+  SetResolver(SetResolver),
+  ResolveTrade(ResolveTrade),
+  RecordObservation,
+  ClaimBatch(ClaimBatch),
 }
 
 // Unpack Instruction
+// Tags follow variant order so Borsh serialization of TradeInstruction is the wire format
 impl TradeInstruction {
+  pub fn pack(&self) -> Vec<u8> {
+    self.try_to_vec().unwrap()
+  }
+
   pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-    let (tag, rest) = input.split_first().ok_or(TradeError::InvalidInstruction)?;
+    let (tag, mut rest) = input.split_first().ok_or(TradeError::InvalidInstruction)?;
     msg!("Tag: {:?}", tag);
     msg!("Expected Length: {:?}", rest.len());
 
-    Ok(match tag {
+    let instruction = match tag {
       0 => Self::CreateUser,
       1 => Self::WithdrawBalance,
-      2 => {
-        let payload = Make::try_from_slice(rest).unwrap();
-        msg!("Payload: {:?}", payload);
-        Self::MakeTrade ( Make {
-            symbol: payload.symbol,
-            slug: payload.slug,
-            contract_size: payload.contract_size,
-            direction: payload.direction,
-            duration: payload.duration,
-          }
-        )
-      },
-      3 => {
-        let payload = Take::try_from_slice(rest).unwrap();
-        msg!("Payload: {:?}", payload);
-        Self::TakeTrade ( Take {
-          direction: payload.direction,
-          }
-        )
-      },
+      2 => Self::MakeTrade(Self::payload(&mut rest)?),
+      3 => Self::TakeTrade(Self::payload(&mut rest)?),
       4 => Self::Claim,
-      _ => return Err(TradeError::InvalidInstruction.into()),
-    })
+      5 => Self::Deposit(Self::payload(&mut rest)?),
+      6 => Self::Withdraw(Self::payload(&mut rest)?),
+      7 => Self::CloseUser,
+      8 => Self::RegisterSessionKey(Self::payload(&mut rest)?),
+      9 => Self::InitializeConfig,
+      10 => Self::SetPause(Self::payload(&mut rest)?),
+      11 => Self::ListMarket(Self::payload(&mut rest)?),
+      12 => Self::SetMarketHalt(Self::payload(&mut rest)?),
+      13 => Self::CancelTrade,
+      14 => Self::ProposeAdmin(Self::payload(&mut rest)?),
+      15 => Self::AcceptAdmin,
+      16 => Self::CreateMultisig(Self::payload(&mut rest)?),
+      17 => Self::SetResolver(Self::payload(&mut rest)?),
+      18 => Self::ResolveTrade(Self::payload(&mut rest)?),
+      19 => Self::RecordObservation,
+      20 => Self::ClaimBatch(Self::payload(&mut rest)?),
+      _ => return Err(TradeError::UnknownInstruction.into()),
+    };
+
+    // Guard: Payload fully consumed
+    if !rest.is_empty() {
+      return Err(TradeError::TrailingInstructionBytes.into());
+    }
+    Ok(instruction)
+  }
+
+  // Decode the next Payload without panicking on malformed input
+  fn payload<T: BorshDeserialize + Debug>(rest: &mut &[u8]) -> Result<T, TradeError> {
+    let input = *rest;
+    let payload = T::deserialize(rest).map_err(|_| {
+      // Zero bytes are a valid encoding of every field, so with zero padding a truncated
+      // Payload decodes past the failing read while an invalid value fails at the same place
+      let failed_at = input.len() - rest.len();
+      let padded = [input, &[0; MAX_PAYLOAD_LEN]].concat();
+      let mut padded_rest = &padded[..];
+      let truncated = T::deserialize(&mut padded_rest).is_ok() || padded.len() - padded_rest.len() > failed_at;
+      if truncated {
+        TradeError::TruncatedInstruction
+      } else {
+        TradeError::InvalidInstructionValue
+      }
+    })?;
+    msg!("Payload: {:?}", payload);
+    Ok(payload)
+  }
+}
+
+
+// TEST MODULE
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn unpack_err(input: &[u8]) -> ProgramError {
+    TradeInstruction::unpack(input).unwrap_err()
+  }
+
+  #[test]
+  fn test_unpack_errors() {
+    let make = TradeInstruction::MakeTrade(Make {
+      symbol: String::from("SOL / USD"),
+      slug: String::from("trade1"),
+      contract_size: ContractSize::Medium,
+      direction: Direction::Long,
+      duration: Duration::FiveMinutes,
+      allowed_taker: Pubkey::default(),
+      reference_price: 0,
+      max_deviation_bps: 0,
+    }).pack();
+    assert!(matches!(TradeInstruction::unpack(&make), Ok(TradeInstruction::MakeTrade(_))));
+
+    // Empty and Unknown Tag
+    assert_eq!(unpack_err(&[]), TradeError::InvalidInstruction.into());
+    assert_eq!(unpack_err(&[21]), TradeError::UnknownInstruction.into());
+    assert_eq!(unpack_err(&[255, 0]), TradeError::UnknownInstruction.into());
+
+    // Truncated Payload (incl. String length beyond the input)
+    assert_eq!(unpack_err(&make[..make.len() - 1]), TradeError::TruncatedInstruction.into());
+    assert_eq!(unpack_err(&[2, 255, 0, 0, 0]), TradeError::TruncatedInstruction.into());
+    assert_eq!(unpack_err(&[5, 1, 0, 0]), TradeError::TruncatedInstruction.into());
+    assert_eq!(unpack_err(&[3]), TradeError::TruncatedInstruction.into());
+
+    // Trailing Bytes (with and without a Payload)
+    assert_eq!(unpack_err(&[make.clone(), vec![0]].concat()), TradeError::TrailingInstructionBytes.into());
+    assert_eq!(unpack_err(&[0, 0]), TradeError::TrailingInstructionBytes.into());
+
+    // Invalid Enum and Bool Values
+    let mut invalid = make.clone();
+    let len = invalid.len();
+    invalid[len - 51] = 5; // Duration (Allowed Taker and Price Protection close the payload)
+    assert_eq!(unpack_err(&invalid), TradeError::InvalidInstructionValue.into());
+    assert_eq!(unpack_err(&[3, 2]), TradeError::InvalidInstructionValue.into());
+    assert_eq!(unpack_err(&[12, 2]), TradeError::InvalidInstructionValue.into());
+    // Invalid UTF-8 and a String length longer than any Instruction
+    assert_eq!(unpack_err(&[2, 1, 0, 0, 0, 255]), TradeError::InvalidInstructionValue.into());
+    assert_eq!(unpack_err(&[2, 255, 255, 255, 255]), TradeError::InvalidInstructionValue.into());
   }
 }
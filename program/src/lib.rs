@@ -4,12 +4,23 @@ pub mod instruction;
 // Manage Accounts state
 pub mod state;
 
+// Program Derived Address seeds shared by Program and Clients
+pub mod pda;
+
 // Process logic
 pub mod processor;
 
+// Owner, Signer, Writable, PDA and Program checks applied to every Account
+pub mod validation;
+
 // Errors
 pub mod error;
 
-// Entrypoint for Solana BPF Loader
-#[cfg(not(feature = "no-entrypoint"))]
+// Structured Events emitted to Program Logs
+pub mod event;
+
+// Instruction Builders and Account Decoders for off-chain Clients
+pub mod client;
+
+// Entrypoint for Solana BPF Loader (the entrypoint! symbol is left out with no-entrypoint)
 pub mod entrypoint;
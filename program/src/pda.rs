@@ -0,0 +1,111 @@
+use solana_program::{bpf_loader_upgradeable, pubkey::{Pubkey, PubkeyError}};
+
+
+// Seeds
+pub const USER_SEED: &[u8] = b"user";
+pub const TRADE_SEED: &[u8] = b"trade";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const MARKET_SEED: &[u8] = b"market";
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const OBSERVATIONS_SEED: &[u8] = b"observations";
+
+// find_* searches for the bump (used once at creation and by clients)
+// create_*_with_bump rebuilds the address from a stored bump (cheap on-chain check)
+
+
+// User Account: [b"user", authority]
+pub fn find_user_address(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[USER_SEED, authority.as_ref()], program_id)
+}
+
+pub fn create_user_address_with_bump(program_id: &Pubkey, authority: &Pubkey, bump: u8) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[USER_SEED, authority.as_ref(), &[bump]], program_id)
+}
+
+// Trade Account: [b"trade", slug, maker authority]
+pub fn find_trade_address(program_id: &Pubkey, slug: &str, authority: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[TRADE_SEED, slug.as_ref(), authority.as_ref()], program_id)
+}
+
+pub fn create_trade_address_with_bump(
+  program_id: &Pubkey,
+  slug: &str,
+  authority: &Pubkey,
+  bump: u8,
+) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[TRADE_SEED, slug.as_ref(), authority.as_ref(), &[bump]], program_id)
+}
+
+// Config Account: [b"config"]
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+pub fn create_config_address_with_bump(program_id: &Pubkey, bump: u8) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[CONFIG_SEED, &[bump]], program_id)
+}
+
+// Market Account: [b"market", symbol]
+pub fn find_market_address(program_id: &Pubkey, symbol: &str) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[MARKET_SEED, symbol.as_ref()], program_id)
+}
+
+pub fn create_market_address_with_bump(program_id: &Pubkey, symbol: &str, bump: u8) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[MARKET_SEED, symbol.as_ref(), &[bump]], program_id)
+}
+
+// Multisig Account: [b"multisig", payer]
+pub fn find_multisig_address(program_id: &Pubkey, payer: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[MULTISIG_SEED, payer.as_ref()], program_id)
+}
+
+pub fn create_multisig_address_with_bump(program_id: &Pubkey, payer: &Pubkey, bump: u8) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[MULTISIG_SEED, payer.as_ref(), &[bump]], program_id)
+}
+
+// Observations Account: [b"observations", symbol]
+pub fn find_observations_address(program_id: &Pubkey, symbol: &str) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[OBSERVATIONS_SEED, symbol.as_ref()], program_id)
+}
+
+pub fn create_observations_address_with_bump(program_id: &Pubkey, symbol: &str, bump: u8) -> Result<Pubkey, PubkeyError> {
+  Pubkey::create_program_address(&[OBSERVATIONS_SEED, symbol.as_ref(), &[bump]], program_id)
+}
+
+// ProgramData Account of the Upgradeable Loader: [program_id] (holds the Upgrade Authority)
+pub fn find_program_data_address(program_id: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+}
+
+
+// TEST MODULE
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_create_with_bump_matches_find() {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    let (user, bump) = find_user_address(&program_id, &authority);
+    assert_eq!(create_user_address_with_bump(&program_id, &authority, bump), Ok(user));
+
+    let (trade, bump) = find_trade_address(&program_id, "trade1", &authority);
+    assert_eq!(create_trade_address_with_bump(&program_id, "trade1", &authority, bump), Ok(trade));
+    assert_ne!(create_trade_address_with_bump(&program_id, "trade2", &authority, bump), Ok(trade));
+
+    let (config, bump) = find_config_address(&program_id);
+    assert_eq!(create_config_address_with_bump(&program_id, bump), Ok(config));
+
+    let (market, bump) = find_market_address(&program_id, "SOL / USD");
+    assert_eq!(create_market_address_with_bump(&program_id, "SOL / USD", bump), Ok(market));
+
+    let (multisig, bump) = find_multisig_address(&program_id, &authority);
+    assert_eq!(create_multisig_address_with_bump(&program_id, &authority, bump), Ok(multisig));
+
+    let (observations, bump) = find_observations_address(&program_id, "SOL / USD");
+    assert_eq!(create_observations_address_with_bump(&program_id, "SOL / USD", bump), Ok(observations));
+    assert_ne!(observations, market);
+  }
+}
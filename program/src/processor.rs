@@ -6,14 +6,33 @@ use solana_program::{
   borsh::try_from_slice_unchecked,
   account_info::{AccountInfo, next_account_info},
   entrypoint::ProgramResult, 
-  pubkey::Pubkey,
+  pubkey::{Pubkey, MAX_SEED_LEN},
   msg,
-  program_error::ProgramError, system_instruction, program::{invoke_signed},
+  program_error::ProgramError, system_instruction, program::{invoke, invoke_signed},
   clock::Clock,
+  bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+};
+use crate::instruction::{
+  TradeInstruction, Make, Take, Deposit, Withdraw, SessionKey, Pause, ListMarket, MarketHalt, ProposeAdmin, CreateMultisig,
+  SetResolver, ResolveTrade, Resolution, ClaimBatch,
+};
+use crate::state::{
+  User, Trade, Config, Market, Multisig, Observation, Observations, MAX_MULTISIG_SIGNERS, MAX_REASON_LEN, OrderStatus,
+  Outcome, Party, MAX_ROUND_DELAY,
+  SESSION_SCOPE_MAKE, SESSION_SCOPE_TAKE, SESSION_SCOPE_CANCEL,
+  PAUSE_MAKE, PAUSE_TAKE, PAUSE_CLAIM, PAUSE_WITHDRAW,
+};
+use crate::pda::{
+  find_user_address, find_trade_address, find_config_address, find_market_address, find_multisig_address,
+  find_observations_address, find_program_data_address, create_user_address_with_bump, create_trade_address_with_bump, create_config_address_with_bump,
+  create_market_address_with_bump, create_observations_address_with_bump,
+  USER_SEED, TRADE_SEED, CONFIG_SEED, MARKET_SEED, MULTISIG_SEED, OBSERVATIONS_SEED,
 };
-use crate::instruction::{TradeInstruction, Make, Take};
-use crate::state::{User, Trade};
 use crate::error::TradeError;
+use crate::event::TradeEvent;
+use crate::validation::{
+  check_signer, check_writable, check_owner, check_system_program, check_chainlink, check_pda,
+};
 
 
 // PROCESSOR
@@ -34,6 +53,18 @@ impl Processor {
     let instruction = TradeInstruction::unpack(instruction_data)?;
     msg!("Instruction Received: {:?}", &instruction);
 
+    // Guard: Global Pause (Config Account passed last) - Cancel, Deposit and Admin paths always allowed
+    let pause_flag = match &instruction {
+      TradeInstruction::MakeTrade (_) => PAUSE_MAKE,
+      TradeInstruction::TakeTrade (_) => PAUSE_TAKE,
+      TradeInstruction::Claim | TradeInstruction::ClaimBatch(_) => PAUSE_CLAIM,
+      TradeInstruction::WithdrawBalance | TradeInstruction::Withdraw (_) | TradeInstruction::CloseUser => PAUSE_WITHDRAW,
+      _ => 0,
+    };
+    if pause_flag != 0 {
+      Self::check_not_paused(program_id, accounts, pause_flag)?;
+    }
+
     // Route Instruction
     match instruction {
         TradeInstruction::CreateUser => {
@@ -42,7 +73,7 @@ impl Processor {
         },
         TradeInstruction::WithdrawBalance => {
           msg!("Instruction: Withdrawing Balance");
-          Self::withdraw_user_account_balance(program_id, accounts)
+          Self::withdraw_user_account_balance(program_id, accounts, None)
         },
         TradeInstruction::MakeTrade (trade) => {
             msg!("Instruction: Make Trade");
@@ -50,12 +81,76 @@ impl Processor {
         },
         TradeInstruction::TakeTrade (trade) => {
             msg!("Instruction: Take Trade");
-            Self::take_trade(program_id, accounts, trade) // trade.direction (placeholder)
+            Self::take_trade(program_id, accounts, trade)
         },
         TradeInstruction::Claim => {
           msg!("Instruction: Claim Trade");
           Self::claim_trade(program_id, accounts)
       },
+        TradeInstruction::Deposit (deposit) => {
+          msg!("Instruction: Depositing Balance");
+          Self::deposit_user_account_balance(program_id, accounts, deposit)
+        },
+        TradeInstruction::Withdraw (withdraw) => {
+          msg!("Instruction: Withdrawing Amount");
+          Self::withdraw_user_account_balance(program_id, accounts, Some(withdraw))
+        },
+        TradeInstruction::CloseUser => {
+          msg!("Instruction: Closing User Account");
+          Self::close_user_pda(program_id, accounts)
+        },
+        TradeInstruction::RegisterSessionKey (session) => {
+          msg!("Instruction: Registering Session Key");
+          Self::register_session_key(program_id, accounts, session)
+        },
+        TradeInstruction::InitializeConfig => {
+          msg!("Instruction: Initializing Config");
+          Self::initialize_config(program_id, accounts)
+        },
+        TradeInstruction::SetPause (pause) => {
+          msg!("Instruction: Setting Pause Flags");
+          Self::set_pause(program_id, accounts, pause)
+        },
+        TradeInstruction::ListMarket (market) => {
+          msg!("Instruction: Listing Market");
+          Self::list_market(program_id, accounts, market)
+        },
+        TradeInstruction::SetMarketHalt (halt) => {
+          msg!("Instruction: Setting Market Halt");
+          Self::set_market_halt(program_id, accounts, halt)
+        },
+        TradeInstruction::CancelTrade => {
+          msg!("Instruction: Cancel Trade");
+          Self::cancel_trade(program_id, accounts)
+        },
+        TradeInstruction::ProposeAdmin (proposal) => {
+          msg!("Instruction: Proposing Admin");
+          Self::propose_admin(program_id, accounts, proposal)
+        },
+        TradeInstruction::AcceptAdmin => {
+          msg!("Instruction: Accepting Admin");
+          Self::accept_admin(program_id, accounts)
+        },
+        TradeInstruction::CreateMultisig (multisig) => {
+          msg!("Instruction: Creating Multisig");
+          Self::create_multisig(program_id, accounts, multisig)
+        },
+        TradeInstruction::SetResolver (resolver) => {
+          msg!("Instruction: Setting Resolver");
+          Self::set_resolver(program_id, accounts, resolver)
+        },
+        TradeInstruction::ResolveTrade (resolve) => {
+          msg!("Instruction: Resolving Trade");
+          Self::resolve_trade(program_id, accounts, resolve)
+        },
+        TradeInstruction::RecordObservation => {
+          msg!("Instruction: Recording Observation");
+          Self::record_observation(program_id, accounts)
+        },
+        TradeInstruction::ClaimBatch(claim_batch) => {
+          msg!("Instruction: Claim Batch");
+          Self::claim_batch(program_id, accounts, claim_batch)
+        },
     }
   }
 
@@ -72,21 +167,17 @@ impl Processor {
     let user_account = next_account_info(account_info_iter)?; // User Account Address (only Public Key Exists at this point)
     let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
 
-    // Guard: Signer
-    if !authority_account.is_signer {
-      return Err(ProgramError::MissingRequiredSignature);
-    }
+    // Guard: Accounts
+    check_signer(authority_account)?;
+    check_writable(authority_account)?;
+    check_writable(user_account)?;
+    check_system_program(system_program)?;
 
     // Generate Program Derived Address (PDA)
-    let (user_pda, user_bump) = Pubkey::find_program_address(
-      &[b"user".as_ref(), authority_account.key.as_ref()],
-      program_id 
-    );
+    let (user_pda, user_bump) = find_user_address(program_id, authority_account.key);
 
     // Guard: Ensure Account Key Received Matches PDA
-    if user_pda != *user_account.key {
-      return Err(TradeError::InvalidUserAccount.into())
-    }
+    check_pda(user_account, Ok(user_pda), TradeError::InvalidUserAccount)?;
 
     // Calculate Lamports needed for PDA
     let rent = Rent::get()?;
@@ -106,40 +197,102 @@ impl Processor {
     invoke_signed(
       create_user_pda_ix,
       &[authority_account.clone(), user_account.clone(), system_program.clone()],
-      &[&[b"user".as_ref(), authority_account.key.as_ref(), &[user_bump]]]
+      &[&[USER_SEED, authority_account.key.as_ref(), &[user_bump]]]
     )?;
 
+    // Initialize User Account State
+    let mut user_account_state = User::try_from_slice(&user_account.data.borrow())?;
+    user_account_state.authority = *authority_account.key;
+    user_account_state.bump = user_bump;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::UserCreated { user: *user_account.key, authority: *authority_account.key }.emit()?;
+
     // Return Result
     msg!("User Account Created");
     Ok(())
   }
 
   
+  // Deposit Account Balance
+  fn deposit_user_account_balance(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    deposit: Deposit,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?; // Holder = User (Users Main Wallet)
+    let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+    let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
+
+    // Guard: Accounts
+    check_signer(authority_account)?;
+    check_writable(authority_account)?;
+    check_system_program(system_program)?;
+
+    // Guard: User Account Owner and PDA (stored bump)
+    Self::load_user(program_id, authority_account.key, user_account)?;
+
+    // Guard: Amount
+    if deposit.amount == 0 {
+      return Err(TradeError::InvalidAmount.into());
+    }
+
+    // Transfer Lamports (invoke as Authority signs)
+    invoke(
+      &system_instruction::transfer(authority_account.key, user_account.key, deposit.amount),
+      &[authority_account.clone(), user_account.clone(), system_program.clone()],
+    )?;
+
+    // Update User Account State
+    let mut user_account_state = User::try_from_slice(&user_account.data.borrow())?;
+    user_account_state.deposited = user_account_state.deposited.checked_add(deposit.amount)
+      .ok_or(TradeError::MathOverflow)?;
+    user_account_state.free_balance = user_account_state.free_balance.checked_add(deposit.amount)
+      .ok_or(TradeError::MathOverflow)?;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::Deposited {
+      user: *user_account.key,
+      amount: deposit.amount,
+      deposited: user_account_state.deposited,
+      free_balance: user_account_state.free_balance,
+    }.emit()?;
+
+    // Return Result
+    Ok(())
+  }
+
+
   // Withdraw Account Balance
+  // Collects the Payouts of the Trade Accounts passed before Config, then
+  // sweeps the full free balance when no Withdraw amount is given
   fn withdraw_user_account_balance(
     program_id: &Pubkey, 
     accounts: &[AccountInfo],
+    withdraw: Option<Withdraw>,
   ) -> ProgramResult {
 
     // Extract Accounts
     let account_info_iter = &mut accounts.iter();
     let authority_account = next_account_info(account_info_iter)?; // Holder = User (Users Main Wallet)
     let user_account = next_account_info(account_info_iter)?; // Holder = User (Users Trading Account)
+    let trade_accounts = account_info_iter.as_slice().split_last().map_or(&[][..], |(_config, trades)| trades); // Settled Trades
 
-    // Guard: Signer
-    if !authority_account.is_signer {
-      return Err(ProgramError::MissingRequiredSignature);
-    }
+    // Guard: Accounts (Config checked by the Pause guard)
+    check_signer(authority_account)?;
+    check_writable(authority_account)?;
 
-    // Generate Program Derived Address (PDA)
-    let (user_pda, _user_bump) = Pubkey::find_program_address(
-      &[b"user".as_ref(), authority_account.key.as_ref()],
-      program_id 
-    );
+    // Guard: User Account Owner and PDA (stored bump)
+    let mut user_account_state = Self::load_user(program_id, authority_account.key, user_account)?;
 
-    // Guard: Ensure Account Key Received Matches PDA
-    if user_pda != *user_account.key {
-      return Err(TradeError::InvalidUserAccount.into())
+    // Collect Payouts into the Free Balance
+    for trade_account in trade_accounts {
+      Self::collect_payout(program_id, user_account, &mut user_account_state, trade_account)?;
     }
 
      // Calculate Lamports needed for PDA ADD BACK IN PRODUCTION !!!!!!!!!!
@@ -147,25 +300,189 @@ impl Processor {
      let rent_lamports = rent.minimum_balance(User::LEN);
     
     // Ensure enough to keep account open
-    if **user_account.try_borrow_lamports()? < rent_lamports {
+    let spare_lamports = user_account.lamports().checked_sub(rent_lamports).ok_or(TradeError::NotEnoughLamports)?;
+
+    // Specify withdrawal amount
+    let tfer_amount = match withdraw {
+      Some(withdraw) => withdraw.amount,
+      None => user_account_state.free_balance.min(spare_lamports),
+    };
+
+    // Guard: Withdrawal must come from Free (not Locked) Balance
+    if tfer_amount > user_account_state.free_balance {
+      msg!("Withdrawal exceeds free balance. Locked: {:?}", user_account_state.locked_balance);
+      return Err(TradeError::InsufficientFreeBalance.into());
+    }
+
+    // Guard: Withdrawal must keep account open
+    if spare_lamports < tfer_amount {
       return Err(TradeError::NotEnoughLamports.into());
     }
 
-    // Specify withdrawal amount
-    let tfer_amount = **user_account.lamports.borrow() - rent_lamports;
+    // Update User Account State
+    user_account_state.free_balance = user_account_state.free_balance.checked_sub(tfer_amount)
+      .ok_or(TradeError::MathOverflow)?;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
 
     // Debit from_account and credit to_account
-    **user_account.try_borrow_mut_lamports()? -= tfer_amount;
-    **authority_account.try_borrow_mut_lamports()? += tfer_amount;
+    Self::transfer_lamports(user_account, authority_account, tfer_amount)?;
+
+    // Emit Event
+    TradeEvent::Withdrawn {
+      user: *user_account.key,
+      amount: tfer_amount,
+      free_balance: user_account_state.free_balance,
+    }.emit()?;
+
+    // Return Result
+    Ok(())
+  }
+
+
+  // Collect the uncollected sides of a User Account on a settled Trade
+  // Moves the Payout lamports to the User Account and releases the Stake
+  fn collect_payout(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    user_account_state: &mut User,
+    trade_account: &AccountInfo,
+  ) -> ProgramResult {
+
+    // Get Trade Account (Owner and PDA)
+    let mut trade_account_state = Self::load_trade(program_id, trade_account)?;
+
+    // Guard: Settled, Expired or Voided with a side of this User not yet collected
+    let sides = trade_account_state.uncollected(user_account.key);
+    if sides.is_empty() {
+      msg!("Nothing to collect on Trade {:?}", trade_account.key);
+      return Err(TradeError::NothingToCollect.into());
+    }
+
+    // Collect each side of this User
+    let trade_lamports = trade_account_state.contract_size.lamports();
+    let mut payout_total: u64 = 0;
+    for side in sides {
+      let (payout, winning_outcome) = match side {
+        Party::Maker => {
+          trade_account_state.maker_collected = true;
+          (trade_account_state.maker_payout, Outcome::Maker)
+        },
+        Party::Taker => {
+          trade_account_state.taker_collected = true;
+          (trade_account_state.taker_payout, Outcome::Taker)
+        },
+      };
+      let result = match trade_account_state.settled_outcome {
+        Outcome::Void => 0,
+        outcome if outcome == winning_outcome => 1,
+        _ => -1,
+      };
+      Self::record_settlement(user_account_state, result, payout, trade_lamports)?;
+      payout_total = payout_total.checked_add(payout).ok_or(TradeError::MathOverflow)?;
+    }
+
+    // Update Trade Account State and move the Payout
+    trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
+    Self::transfer_lamports(trade_account, user_account, payout_total)?;
+
+    // Emit Event
+    TradeEvent::PayoutCollected { trade: *trade_account.key, user: *user_account.key, payout: payout_total }.emit()?;
+    Ok(())
+  }
+
+
+  // Close User
+  fn close_user_pda(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?; // Holder = User (Users Main Wallet)
+    let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Accounts (Config checked by the Pause guard)
+    check_signer(authority_account)?;
+    check_writable(authority_account)?;
+
+    // Guard: User Account Owner and PDA (stored bump)
+    let user_account_state = Self::load_user(program_id, authority_account.key, user_account)?;
+
+    // Guard: No Open or InPlay Trades
+    if user_account_state.open_positions != 0 {
+      msg!("Open Positions: {:?}", user_account_state.open_positions);
+      return Err(TradeError::OpenPositionsExist.into());
+    }
+
+    // Sweep all Lamports (including rent) back to Authority
+    let tfer_amount = user_account.lamports();
+    Self::transfer_lamports(user_account, authority_account, tfer_amount)?;
+
+    // Zero Account Data
+    user_account.data.borrow_mut().fill(0);
+
+    // Emit Event
+    TradeEvent::UserClosed { user: *user_account.key, lamports: tfer_amount }.emit()?;
+
+    // Return Result
+    msg!("User Account Closed");
+    Ok(())
+  }
+
+
+  // Register Session Key
+  // Delegate may Make, Take or Cancel within scope until expiry (never Withdraw)
+  fn register_session_key(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    session: SessionKey,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?; // Holder = User (Users Main Wallet)
+    let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Accounts
+    check_signer(authority_account)?;
+
+    // Guard: User Account Owner and PDA (stored bump)
+    let mut user_account_state = Self::load_user(program_id, authority_account.key, user_account)?;
+
+    // Guard: Scope limited to Make, Take and Cancel
+    if session.scope & !(SESSION_SCOPE_MAKE | SESSION_SCOPE_TAKE | SESSION_SCOPE_CANCEL) != 0 {
+      return Err(TradeError::InvalidSessionScope.into());
+    }
+
+    // Guard: Session Key cannot be the Authority itself
+    if session.session_key == *authority_account.key {
+      return Err(TradeError::InvalidSessionKey.into());
+    }
+
+    // Update User Account State (default key or zero expiry revokes)
+    user_account_state.session_key = session.session_key;
+    user_account_state.session_expiry = session.expiry;
+    user_account_state.session_scope = session.scope;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::SessionKeyRegistered {
+      user: *user_account.key,
+      session_key: session.session_key,
+      expiry: session.expiry,
+      scope: session.scope,
+    }.emit()?;
 
     // Return Result
+    msg!("Session Key Registered: {:?} until {:?}", session.session_key, session.expiry);
     Ok(())
   }
 
 
   // Claim Trade
   fn claim_trade(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
   ) -> ProgramResult {
 
@@ -173,172 +490,357 @@ impl Processor {
     let account_info_iter = &mut accounts.iter();
     let claimer_authority_account = next_account_info(account_info_iter)?; // Holder = User
     let trade_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Created by Maker
-    let maker_account = next_account_info(account_info_iter)?;
-    let taker_account = next_account_info(account_info_iter)?;
-    let _system_program = next_account_info(account_info_iter)?; // Not used but sent in as PDA Trade Account owner
+    let system_program = next_account_info(account_info_iter)?; // Not used but sent in as PDA Trade Account owner
     let chainlink_feed_account = next_account_info(account_info_iter)?; // Chainlink Price Feed Data Account
     let chainlink_program = next_account_info(account_info_iter)?; // Chainlink Program Account
+    let observations_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Market Observations
+
+    // Guard: Accounts (Config checked by the Pause guard)
+    check_signer(claimer_authority_account)?;
+    check_system_program(system_program)?;
+    check_chainlink(chainlink_feed_account, chainlink_program)?;
+
+    // Claim
+    let unix_current = Self::unix_now()?;
+    Self::claim_trade_account(
+      program_id, trade_account, chainlink_feed_account, chainlink_program, observations_account, &mut None, unix_current,
+    )
+  }
 
-    // Guard: Signer
-    if !claimer_authority_account.is_signer {
-      return Err(ProgramError::MissingRequiredSignature);
+
+  // Claim Batch
+  // Claims every eligible Trade Account passed before Config against one Chainlink round
+  // Ineligible Trades are skipped, each Trade logs a BatchClaimResult
+  // No User Accounts needed: Payouts stay on the Trade until collected with a Withdraw
+  fn claim_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    claim_batch: ClaimBatch,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let claimer_authority_account = next_account_info(account_info_iter)?; // Holder = User
+    let system_program = next_account_info(account_info_iter)?; // Not used but sent in as PDA Trade Account owner
+    let chainlink_feed_account = next_account_info(account_info_iter)?; // Chainlink Price Feed Data Account
+    let chainlink_program = next_account_info(account_info_iter)?; // Chainlink Program Account
+    let observations_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Market Observations
+    let trade_accounts = account_info_iter.as_slice().split_last().map_or(&[][..], |(_config, trades)| trades); // Expired Trades
+
+    // Guard: Accounts (Config checked by the Pause guard)
+    check_signer(claimer_authority_account)?;
+    check_system_program(system_program)?;
+    check_chainlink(chainlink_feed_account, chainlink_program)?;
+    if trade_accounts.is_empty() {
+      return Err(ProgramError::NotEnoughAccountKeys);
     }
 
-    // Get Trade Account
-    let mut trade_account_state = try_from_slice_unchecked::<Trade>(&trade_account.data.borrow())?;
+    // Get the requested Round: the live Chainlink round, or one recorded in the Market's Observations
+    let latest_round = chainlink::latest_round_data(chainlink_program.clone(), chainlink_feed_account.clone())?;
+    let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
+    let (round_answer, round_timestamp) = if latest_round.round_id == claim_batch.round_id {
+      (latest_round.answer, latest_round.timestamp)
+    } else {
+      let observations_account_state = Self::load_observations(program_id, observations_account, &chainlink_asset_description)?;
+      let observation = observations_account_state.samples.iter()
+        .find(|sample| sample.round_id == claim_batch.round_id)
+        .ok_or(TradeError::RoundNotFound)?;
+      (observation.price, observation.unix_timestamp)
+    };
+    msg!("Round {:?} at {:?}: {:?}", claim_batch.round_id, round_timestamp, round_answer);
+
+    // Claim each Trade on the Round
+    let unix_current = Self::unix_now()?;
+    let mut chainlink_round = Some((chainlink_asset_description, round_answer, round_timestamp));
+    let mut claimed: usize = 0;
+    for trade_account in trade_accounts {
+      let result = Self::claim_trade_account(
+        program_id, trade_account, chainlink_feed_account, chainlink_program, observations_account,
+        &mut chainlink_round, unix_current,
+      );
+      let error = match result {
+        Ok(()) => {
+          claimed += 1;
+          0
+        },
+        Err(error) => {
+          msg!("Skipped Trade {:?}: {:?}", trade_account.key, error);
+          u64::from(error)
+        },
+      };
+      TradeEvent::BatchClaimResult { trade: *trade_account.key, error }.emit()?;
+    }
+
+    // Return Result
+    msg!("Claimed {:?} of {:?} Trades", claimed, trade_accounts.len());
+    Ok(())
+  }
+
+
+  // Claim a single Trade Account
+  // Leaves the Trade Account untouched on error (state is written last)
+  // chainlink_round caches the Feed description, answer and timestamp (set up front by a batch)
+  fn claim_trade_account<'a>(
+    program_id: &Pubkey,
+    trade_account: &AccountInfo,
+    chainlink_feed_account: &AccountInfo<'a>,
+    chainlink_program: &AccountInfo<'a>,
+    observations_account: &AccountInfo,
+    chainlink_round: &mut Option<(String, i128, u32)>,
+    unix_current: u32,
+  ) -> ProgramResult {
+
+    // Get Trade Account (Owner and PDA)
+    let mut trade_account_state = Self::load_trade(program_id, trade_account)?;
 
-    // Guard: Ensure Trade is in InPlay status (i.e. not claimed by started)
-    if trade_account_state.order_status != 2 {
+    // Guard: Ensure Trade is InPlay or awaiting payout (i.e. not claimed but started)
+    if !matches!(trade_account_state.order_status, OrderStatus::InPlay | OrderStatus::Pending) {
       msg!("Trade not in correct order status");
       return Err(TradeError::InvalidTradeForClaim.into())
     }
 
-    // Guard: Ensure Trade Account Details Match Maker
-    if trade_account_state.maker != *maker_account.key {
-      msg!("Maker Details do Not Match");
-      return Err(TradeError::InvalidTradeAccount.into())
-    }
-
-    // Guard: Ensure Trade Account Details Match Taker
-    if trade_account_state.taker != *taker_account.key {
-      msg!("Maker Details do Not Match");
-      return Err(TradeError::InvalidTradeAccount.into())
+    // Pending: pay the recorded Closing Price once the challenge window has passed (no Oracle needed)
+    if trade_account_state.order_status == OrderStatus::Pending {
+      if unix_current < Self::unix_final(&trade_account_state)? {
+        msg!("Claim can be voided or corrected until the challenge window closes.");
+        return Err(TradeError::ChallengeWindowOpen.into())
+      }
+      return Self::settle_trade(trade_account, trade_account_state);
     }
 
-    // Get Clock
-    let clock = Clock::get()?;
-    let unix_current = clock.unix_timestamp as u32;
-  
     // Guard: Time Check
     if unix_current < trade_account_state.unix_end {
       msg!("Too early to claim funds. Wait for contract to expire.");
       return Err(TradeError::InvalidTimeForClaim.into())
     }
 
-    // Get Chainlink Price - // REMEMBER ONLY WORKS ON DEVNET
-    let chainlink_round = chainlink::latest_round_data(chainlink_program.clone(), chainlink_feed_account.clone())?;
-    let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
-    let _chainlink_decimals = chainlink::decimals(chainlink_program.clone(), chainlink_feed_account.clone())?;
-    let chainlink_price = chainlink_round.answer;
-
-    // Guard: Ensure Asset Matches on Trade Account
-    if chainlink_asset_description != trade_account_state.symbol {
-      msg!("Chainlink Symbol Mismatch to Account Provided");
-      return Err(TradeError::ChainlinkMismatch.into())
+    // Guard: A batch settles only Trades priced by a single round, on the first Round at or after the end
+    // (a single Claim reads the live round)
+    if let Some((_, _, round_timestamp)) = chainlink_round {
+      let unix_end = trade_account_state.unix_end;
+      if trade_account_state.twap_window != 0 || trade_account_state.max_observation_age != 0 {
+        msg!("Trade settles on Observations, Claim it alone");
+        return Err(TradeError::TradeNotPricedByRound.into())
+      }
+      if unix_end > *round_timestamp {
+        msg!("Round at {:?} is before the Trade end {:?}", round_timestamp, unix_end);
+        return Err(TradeError::RoundBeforeTradeEnd.into())
+      }
+      let observations_account_state = Self::load_observations(program_id, observations_account, &trade_account_state.symbol)?;
+      let earlier_round = observations_account_state.samples.iter()
+        .any(|sample| (unix_end..*round_timestamp).contains(&sample.unix_timestamp));
+      if *round_timestamp - unix_end > MAX_ROUND_DELAY || earlier_round {
+        msg!("Round at {:?} is not the first after the Trade end {:?}", round_timestamp, unix_end);
+        return Err(TradeError::RoundTooLate.into())
+      }
     }
 
-    // Guard: Ensure Chainlink Price
-    if chainlink_price == 0 {
+    // Closing Price: average Observation over the TWAP window before the end, the Observation as of the end when
+    // fresh enough, or the latest Chainlink round
+    let closing_price = if trade_account_state.twap_window != 0 {
+      let observations_account_state = Self::load_observations(program_id, observations_account, &trade_account_state.symbol)?;
+      let unix_from = trade_account_state.unix_end.saturating_sub(trade_account_state.twap_window);
+      observations_account_state.twap(unix_from, trade_account_state.unix_end)?
+    } else if let Some(price) = Self::observed_price(
+      program_id, observations_account, &trade_account_state.symbol, trade_account_state.unix_end,
+      trade_account_state.max_observation_age,
+    )? {
+      price
+    } else {
+
+      // Get Chainlink Price - // REMEMBER ONLY WORKS ON DEVNET
+      let (chainlink_asset_description, chainlink_answer, _) = match chainlink_round {
+        Some(round) => round.clone(),
+        None => {
+          let latest_round = chainlink::latest_round_data(chainlink_program.clone(), chainlink_feed_account.clone())?;
+          let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
+          let _chainlink_decimals = chainlink::decimals(chainlink_program.clone(), chainlink_feed_account.clone())?;
+          chainlink_round.insert((chainlink_asset_description, latest_round.answer, latest_round.timestamp)).clone()
+        },
+      };
+
+      // Guard: Ensure Asset Matches on Trade Account
+      if chainlink_asset_description != trade_account_state.symbol {
+        msg!("Chainlink Symbol Mismatch to Account Provided");
+        return Err(TradeError::ChainlinkMismatch.into())
+      }
+      chainlink_answer
+    };
+
+    // Guard: Ensure Closing Price
+    if closing_price == 0 {
       msg!("Chainlink Price Received as Zero. Chainlink Data Issue.");
       return Err(TradeError::ChainlinkDataIssue.into())
     }
 
-    // Determine Winner
-    let winner: String;
-    let mut payee = maker_account;
-    let unix_thresh = trade_account_state.unix_end + (1 * 24 * 60 * 60); // 24 hour allowance
-    if (trade_account_state.direction == 0) & (chainlink_price > trade_account_state.benchmark_price) {
-      winner = String::from("Maker");
-    } else if (trade_account_state.direction == 1) & (chainlink_price < trade_account_state.benchmark_price) {
-      winner = String::from("Maker");
-    } else if (chainlink_price == trade_account_state.benchmark_price) || (unix_current >= unix_thresh) {
-      winner = String::from("Draw");
-    } else {
-      winner = String::from("Taker");
-      payee = taker_account;
+    // Record Closing Price (late when claimed claim_window or more after the end)
+    trade_account_state.closing_price = closing_price;
+    trade_account_state.unix_claimed = unix_current;
+    trade_account_state.late = trade_account_state.claim_window != 0 && unix_current >= trade_account_state.unix_end
+      .checked_add(trade_account_state.claim_window).ok_or(TradeError::MathOverflow)?;
+
+    // Pay now without a challenge window
+    if trade_account_state.challenge_window == 0 {
+      return Self::settle_trade(trade_account, trade_account_state);
     }
 
+    // Update Trade Account (payout after the challenge window)
+    trade_account_state.order_status.transition(OrderStatus::Pending)?;
+    trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::TradeClaimed {
+      trade: *trade_account.key,
+      closing_price,
+      unix_final: Self::unix_final(&trade_account_state)?,
+    }.emit()?;
+
+    // Return Result
+    Ok(())
+  }
+
+
+  // Settle Trade
+  // Records the Payouts of the recorded Closing Price: Settled, or Expired when claimed late
+  fn settle_trade(
+    trade_account: &AccountInfo,
+    mut trade_account_state: Trade,
+  ) -> ProgramResult {
+
+    // Determine Outcome
+    let outcome = trade_account_state.outcome();
+    msg!("Outcome: {:?}", outcome);
+
     // Update Trade Account
-    trade_account_state.order_status = 3;
-    trade_account_state.closing_price = chainlink_price;
+    let next_status = if trade_account_state.late { OrderStatus::Expired } else { OrderStatus::Settled };
+    trade_account_state.order_status.transition(next_status)?;
+    let payout = Self::record_payouts(trade_account, &mut trade_account_state, outcome)?;
     trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
 
+    // Emit Event
+    TradeEvent::TradeSettled {
+      trade: *trade_account.key,
+      winner: match outcome { Outcome::Maker => 0, Outcome::Taker => 1, Outcome::Void => 2 },
+      closing_price: trade_account_state.closing_price,
+      payout,
+    }.emit()?;
+
+    // Return Result
+    Ok(())
+  }
+
+
+  // Record the Payouts of an Outcome: exact Stakes, anything above them is dust for the Market's recipient
+  // Lamports stay on the Trade Account until each User collects its side with a Withdraw
+  // Returns the lamports to be paid out
+  fn record_payouts(
+    trade_account: &AccountInfo,
+    trade_account_state: &mut Trade,
+    outcome: Outcome,
+  ) -> Result<u64, ProgramError> {
+
     // Calculate Lamports needed for PDA
     let rent = Rent::get()?;
     let rent_lamports = rent.minimum_balance(Trade::LEN);
 
-    // Pay Winner
-    let tfer_amount = **trade_account.lamports.borrow() - rent_lamports;
-    msg!("Lamports to Transfer: {:?}", tfer_amount);
-
-    **trade_account.try_borrow_mut_lamports()? -= tfer_amount;
-    if winner != "Draw" {
-      **payee.try_borrow_mut_lamports()? += tfer_amount;
-    } else {
-      **maker_account.try_borrow_mut_lamports()? += tfer_amount / 2;
-      **taker_account.try_borrow_mut_lamports()? += tfer_amount / 2;
+    // Split the Pot
+    let trade_lamports = trade_account_state.contract_size.lamports();
+    let pot = trade_lamports.checked_mul(2).ok_or(TradeError::MathOverflow)?;
+    let tfer_amount = trade_account.lamports().checked_sub(rent_lamports).ok_or(TradeError::MathOverflow)?;
+    let dust = tfer_amount.checked_sub(pot).ok_or(TradeError::MathOverflow)?;
+    let (mut maker_payout, mut taker_payout) = match outcome {
+      Outcome::Maker => (pot, 0),
+      Outcome::Taker => (0, pot),
+      Outcome::Void => (trade_lamports, trade_lamports),
+    };
+    match trade_account_state.dust_recipient {
+      Party::Maker => maker_payout += dust,
+      Party::Taker => taker_payout += dust,
     }
+    msg!("Lamports to Transfer: {:?} (Dust {:?})", tfer_amount, dust);
 
-    // Return Result
-    Ok(())
+    // Update Trade Account State
+    trade_account_state.settled_outcome = outcome;
+    trade_account_state.maker_payout = maker_payout;
+    trade_account_state.taker_payout = taker_payout;
+    Ok(tfer_amount)
   }
-  
+
 
   // Take Trade
   fn take_trade(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    _trade: Take,
+    take: Take,
   ) -> ProgramResult {
 
     // Extract Accounts
     let account_info_iter = &mut accounts.iter();
-    let taker_authority_account = next_account_info(account_info_iter)?; // Holder = User
+    let taker_signer_account = next_account_info(account_info_iter)?; // Holder = User (Authority or Session Key)
     let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) 
     let trade_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Created by Maker
-    let _system_program = next_account_info(account_info_iter)?; // Noy used but sent in as PDA Trade Account owner
+    let system_program = next_account_info(account_info_iter)?; // Not used but sent in as PDA Trade Account owner
     let chainlink_feed_account = next_account_info(account_info_iter)?; // Chainlink Price Feed Data Account
     let chainlink_program = next_account_info(account_info_iter)?; // Chainlink Program Account
+    let market_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Listed by Admin
 
-    // Guard: Signer
-    if !taker_authority_account.is_signer {
-      return Err(ProgramError::MissingRequiredSignature);
-    }
+    // Guard: Signer (Authority or Session Key) - To check user is payer
+    let authority = Self::verify_trade_signer(program_id, taker_signer_account, user_account, SESSION_SCOPE_TAKE)?;
+
+    // Guard: Accounts (Market and Config checked below and by the Pause guard)
+    check_system_program(system_program)?;
+    check_chainlink(chainlink_feed_account, chainlink_program)?;
 
     // Get Clock
-    let clock = Clock::get()?;
-    let unix_start = clock.unix_timestamp as u32;
-    let mut unix_end = unix_start;
+    let unix_start = Self::unix_now()?;
 
-    // Get Trade Account
-    let mut trade_account_state = try_from_slice_unchecked::<Trade>(&trade_account.data.borrow())?;
+    // Get Trade Account (Owner and PDA)
+    let mut trade_account_state = Self::load_trade(program_id, trade_account)?;
 
     // Guard: Ensure A Match is Not Already Existing
-    if trade_account_state.order_status != 1 {
+    if trade_account_state.order_status != OrderStatus::Open {
       return Err(TradeError::AlreadyExistingTrade.into())
     }
 
-    // Generate Program Derived Address (PDA) - To check user is payer
-    let (user_pda, _user_bump) = Pubkey::find_program_address(
-      &[b"user".as_ref(), taker_authority_account.key.as_ref()],
-      program_id 
-    );
+    // Guard: Price Bounds are non negative and ordered
+    if take.min_price < 0 || take.max_price < 0 || (take.max_price != 0 && take.min_price > take.max_price) {
+      return Err(TradeError::InvalidPriceProtection.into())
+    }
 
-    // Guard: Ensure Account Key Received Matches PDA - To check user is payer
-    if user_pda != *user_account.key {
-      return Err(TradeError::InvalidUserAccount.into())
+    // Guard: Taker takes the other side of the Trade they saw
+    if take.direction != trade_account_state.direction.opposite() {
+      msg!("Taker Direction {:?} against Maker Direction {:?}", take.direction, trade_account_state.direction);
+      return Err(TradeError::InvalidTakerDirection.into())
     }
 
-    // Calculate Trade Lamports for Trade Contracts
-    let trade_lamports: u64;
-    match trade_account_state.contract_size {
-      0 => {trade_lamports = 1_000_000_00}, // 0.1 SOL
-      1 => {trade_lamports = 1_000_000_000}, // 1 SOL
-      5 => {trade_lamports = 5_000_000_000}, // 5 SOL
-      _ => return Err(TradeError::InvalidContractSize.into())
+    // Guard: No Self Trading
+    if trade_account_state.maker == *user_account.key {
+      return Err(TradeError::SelfTrade.into())
     }
 
+    // Guard: OTC Trade only Taken by the allowed Taker Authority
+    if trade_account_state.allowed_taker != Pubkey::default() && trade_account_state.allowed_taker != authority {
+      return Err(TradeError::TakerNotAllowed.into())
+    }
+
+    // Guard: Market Listed and not Halted
+    let market_account_state = Self::check_market_open(program_id, market_account, &trade_account_state.symbol)?;
+
+    // Calculate Trade Lamports for Trade Contracts
+    let trade_lamports = trade_account_state.contract_size.lamports();
+
+    // Benchmark Price: always the live Chainlink round (an older Observation would let the Taker pick a stale price)
     // Get Chainlink Price -  // REMEMBER ONLY WORKS ON DEVNET
     let chainlink_round = chainlink::latest_round_data(chainlink_program.clone(), chainlink_feed_account.clone())?;
     let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
     let _chainlink_decimals = chainlink::decimals(chainlink_program.clone(), chainlink_feed_account.clone())?;
-    let chainlink_price = chainlink_round.answer;
 
     // Guard: Ensure Asset Matches on Trade Account
     if chainlink_asset_description != trade_account_state.symbol {
       msg!("Chainlink Symbol Mismatch to Account Provided");
       return Err(TradeError::ChainlinkMismatch.into())
     }
+    let chainlink_price = chainlink_round.answer;
 
     // Guard: Ensure Chainlink Price
     if chainlink_price == 0 {
@@ -346,36 +848,59 @@ impl Processor {
       return Err(TradeError::ChainlinkDataIssue.into())
     }
 
+    // Guard: Benchmark within the Taker's slippage bounds
+    if (take.min_price != 0 && chainlink_price < take.min_price) || (take.max_price != 0 && chainlink_price > take.max_price) {
+      msg!("Benchmark Price {:?} outside [{:?}, {:?}]", chainlink_price, take.min_price, take.max_price);
+      return Err(TradeError::TakerPriceOutOfBounds.into())
+    }
+
+    // Guard: Benchmark within the Maker's deviation from the Reference Price
+    if !trade_account_state.within_reference(chainlink_price)? {
+      msg!("Benchmark Price {:?} outside {:?} bps of {:?}",
+        chainlink_price, trade_account_state.max_deviation_bps, trade_account_state.reference_price);
+      return Err(TradeError::MakerPriceOutOfBounds.into())
+    }
+
     // Update Trade Account
     trade_account_state.taker = *user_account.key;
     trade_account_state.unix_start = unix_start;
     trade_account_state.benchmark_price = chainlink_price;
-    trade_account_state.order_status = 2;
-    trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
-    match trade_account_state.duration {
-      0 => {unix_end += 5 * 60},
-      1 => {unix_end += 60 * 60},
-      2 => {unix_end += 24 * 60 * 60},
-      _ => {return Err(TradeError::InvalidDurationCalculation.into())}
-    }
-    trade_account_state.unix_end += unix_end;
+    trade_account_state.order_status.transition(OrderStatus::InPlay)?;
+    trade_account_state.unix_end = market_account_state.unix_end(trade_account_state.duration, unix_start)?;
     trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
 
     // Update User Account State
     let mut user_account_state = User::try_from_slice(&user_account.data.borrow())?;
-    user_account_state.trades_placed += 1;
-    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
+    user_account_state.trades_placed = user_account_state.trades_placed.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    user_account_state.trades_taken = user_account_state.trades_taken.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    user_account_state.total_volume = user_account_state.total_volume.checked_add(trade_lamports)
+      .ok_or(TradeError::MathOverflow)?;
 
     // Guard: Transfer Lamports check
     msg!("Lamports being sent: {:?}", trade_lamports);
+    if user_account_state.free_balance < trade_lamports {
+      msg!("Not enough free balance");
+      return Err(TradeError::InsufficientFreeBalance.into());
+    }
+    Self::lock_stake(&mut user_account_state, trade_lamports)?;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
     if **user_account.try_borrow_lamports()? < trade_lamports {
       msg!("Not enough SOL (lamports)");
       return Err(TradeError::NotEnoughLamports.into());
     }
 
     // Transfer Lamports
-    **user_account.try_borrow_mut_lamports()? -= trade_lamports; // Not owned by Program (thus Signed)
-    **trade_account.try_borrow_mut_lamports()? += trade_lamports; // Owner by Program
+    Self::transfer_lamports(user_account, trade_account, trade_lamports)?;
+
+    // Emit Event
+    TradeEvent::TradeTaken {
+      trade: *trade_account.key,
+      taker: *user_account.key,
+      stake: trade_lamports,
+      benchmark_price: trade_account_state.benchmark_price,
+      unix_start: trade_account_state.unix_start,
+      unix_end: trade_account_state.unix_end,
+    }.emit()?;
 
     // Return
     Ok(())
@@ -391,34 +916,48 @@ impl Processor {
 
     // extract Accounts
     let account_info_iter = &mut accounts.iter();
-    let authority_account = next_account_info(account_info_iter)?; // Holder = User
+    let signer_account = next_account_info(account_info_iter)?; // Holder = User (Authority or Session Key)
     let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
     let trade_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
     let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
     let chainlink_feed_account = next_account_info(account_info_iter)?; // Chainlink Price Feed Data Account
     let chainlink_program = next_account_info(account_info_iter)?; // Chainlink Program Account
+    let market_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Listed by Admin
 
-    // Guard: Signer
-    if !authority_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    // Guard: Signer (Authority or Session Key) and User Account
+    let authority = Self::verify_trade_signer(program_id, signer_account, user_account, SESSION_SCOPE_MAKE)?;
+
+    // Guard: Accounts (Signer pays for the Trade Account, Config checked by the Pause guard)
+    check_writable(signer_account)?;
+    check_writable(trade_account)?;
+    check_system_program(system_program)?;
+    check_chainlink(chainlink_feed_account, chainlink_program)?;
+
+    // Guard: Reference Price is non negative
+    if trade.reference_price < 0 {
+      return Err(TradeError::InvalidPriceProtection.into())
     }
 
-    // Guard: User Account Owner
-    if user_account.owner != program_id {
-      msg!("Greeted account does not have the correct program id");
-      return Err(ProgramError::IncorrectProgramId);
+    // Guard: Slug fits a PDA seed
+    if trade.slug.len() > MAX_SEED_LEN {
+      msg!("Slug longer than {:?} bytes", MAX_SEED_LEN);
+      return Err(TradeError::SeedTooLong.into())
     }
 
+    // Guard: Market Listed and not Halted
+    let market_account_state = Self::check_market_open(program_id, market_account, &trade.symbol)?;
+
+    // Get Clock
+    let unix_created = Self::unix_now()?;
+
+    // Guard: Duration within the Market bounds (Expiry on the Market calendar)
+    market_account_state.unix_end(trade.duration, unix_created)?;
+
     // Generate Program Derived Address (PDA)
-    let (trade_pda, trade_bump) = Pubkey::find_program_address(
-        &[b"trade".as_ref(), trade.slug.as_ref(), authority_account.key.as_ref()],
-        program_id 
-    );
+    let (trade_pda, trade_bump) = find_trade_address(program_id, &trade.slug, &authority);
 
     // Guard: Ensure Account Key Received Matches PDA
-    if trade_pda != *trade_account.key {
-      return Err(TradeError::InvalidTradeAccount.into())
-    }
+    check_pda(trade_account, Ok(trade_pda), TradeError::InvalidTradeAccount)?;
 
     // Get Chainlink Description - // REMEMBER ONLY WORKS ON DEVNET
     let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
@@ -437,7 +976,7 @@ impl Processor {
 
     // Build Transaction for Trade PDA Account Creation
     let create_trade_pda_ix = &system_instruction::create_account(
-      authority_account.key,
+      signer_account.key,
       trade_account.key,
       rent_lamports,
       Trade::LEN.try_into().unwrap(),
@@ -448,14 +987,10 @@ impl Processor {
     msg!("Creating Trade account");
     invoke_signed(
       create_trade_pda_ix, 
-      &[authority_account.clone(), trade_account.clone(), system_program.clone()],
-      &[&[b"trade".as_ref(), trade.slug.as_ref(), authority_account.key.as_ref(), &[trade_bump]]]
+      &[signer_account.clone(), trade_account.clone(), system_program.clone()],
+      &[&[TRADE_SEED, trade.slug.as_ref(), authority.as_ref(), &[trade_bump]]]
     )?;
 
-    // Get Clock
-    let clock = Clock::get()?;
-    let unix_created = clock.unix_timestamp as u32;
-
     // Get Current Trade Account State
     // Use Unchecked if working with Strings
     let mut trade_account_state = try_from_slice_unchecked::<Trade>(&trade_account.data.borrow())?;
@@ -464,56 +999,979 @@ impl Processor {
     trade_account_state.maker = *user_account.key;
     trade_account_state.trade_account = *trade_account.key;
     trade_account_state.bump = trade_bump;
+    trade_account_state.maker_authority = authority;
     trade_account_state.slug = trade.slug;
     trade_account_state.unix_created = unix_created;
     trade_account_state.symbol = trade.symbol;
     trade_account_state.contract_size = trade.contract_size;
     trade_account_state.direction = trade.direction;
     trade_account_state.duration = trade.duration;
-    trade_account_state.order_status = 1; // see state
+    trade_account_state.allowed_taker = trade.allowed_taker;
+    trade_account_state.reference_price = trade.reference_price;
+    trade_account_state.max_deviation_bps = trade.max_deviation_bps;
+    trade_account_state.tie_outcome = market_account_state.tie_outcome;
+    trade_account_state.late_outcome = market_account_state.late_outcome;
+    trade_account_state.claim_window = market_account_state.claim_window;
+    trade_account_state.dust_recipient = market_account_state.dust_recipient;
+    trade_account_state.challenge_window = market_account_state.challenge_window;
+    trade_account_state.twap_window = market_account_state.twap_window;
+    trade_account_state.max_observation_age = market_account_state.max_observation_age;
+    trade_account_state.order_status.transition(OrderStatus::Open)?;
 
     // Update State with New Trade
     msg!("Serializing Trade data");
     trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
 
+    // Fund the Trade from User Account
+    // Calculate Trade Lamports for Trade Contracts
+    let trade_lamports = trade.contract_size.lamports();
+
     // Update User Account State
     let mut user_account_state = User::try_from_slice(&user_account.data.borrow())?;
-    user_account_state.trades_placed += 1;
+    user_account_state.trades_placed = user_account_state.trades_placed.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    user_account_state.trades_made = user_account_state.trades_made.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    user_account_state.total_volume = user_account_state.total_volume.checked_add(trade_lamports)
+      .ok_or(TradeError::MathOverflow)?;
+
+    // Guard: Stake must come from Free Balance
+    if user_account_state.free_balance < trade_lamports {
+      return Err(TradeError::InsufficientFreeBalance.into());
+    }
+    Self::lock_stake(&mut user_account_state, trade_lamports)?;
     user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
 
-    // Fund the Trade from User Account
-    // Calculate Trade Lamports for Trade Contracts
-    let trade_lamports: u64;
-    match trade.contract_size {
-      0 => {trade_lamports = 1_000_000_00}, // 0.1 SOL
-      1 => {trade_lamports = 1_000_000_000}, // 1 SOL
-      5 => {trade_lamports = 5_000_000_000}, // 5 SOL
-      _ => return Err(TradeError::InvalidContractSize.into())
+    // Ensure enough to keep account open
+    if **user_account.try_borrow_lamports()? < trade_lamports {
+      return Err(TradeError::NotEnoughLamports.into());
+    }
+
+    // Debit from_account and credit to_account
+    Self::transfer_lamports(user_account, trade_account, trade_lamports)?;
+
+    // Emit Event
+    TradeEvent::TradeMade {
+      trade: *trade_account.key,
+      maker: *user_account.key,
+      symbol: trade_account_state.symbol,
+      contract_size: trade_account_state.contract_size,
+      direction: trade_account_state.direction,
+      duration: trade_account_state.duration,
+      stake: trade_lamports,
+      unix_created,
+      allowed_taker: trade_account_state.allowed_taker,
+    }.emit()?;
+
+    // Return
+    msg!("Open Order Created");
+    Ok(())
+  }
+
+
+  // Initialize Config
+  // The Program's Upgrade Authority becomes Admin
+  fn initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Config Address (only Public Key Exists at this point)
+    let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
+    let program_data_account = next_account_info(account_info_iter)?; // Holder = Upgradeable Loader (ProgramData of this Program)
+
+    // Guard: Accounts
+    check_signer(admin_account)?;
+    check_writable(admin_account)?;
+    check_writable(config_account)?;
+    check_system_program(system_program)?;
+    check_owner(program_data_account, &bpf_loader_upgradeable::id())?;
+    check_pda(program_data_account, Ok(find_program_data_address(program_id).0), TradeError::InvalidProgramDataAccount)?;
+
+    // Guard: Only the Upgrade Authority may become Admin (the Config could otherwise be front-run after deploy)
+    let upgrade_authority = match bincode::deserialize(&program_data_account.data.borrow()) {
+      Ok(UpgradeableLoaderState::ProgramData { upgrade_authority_address, .. }) => upgrade_authority_address,
+      _ => return Err(TradeError::InvalidProgramDataAccount.into()),
+    };
+    if upgrade_authority != Some(*admin_account.key) {
+      msg!("Admin {:?} is not the Upgrade Authority {:?}", admin_account.key, upgrade_authority);
+      return Err(TradeError::NotUpgradeAuthority.into());
     }
 
     // Generate Program Derived Address (PDA)
-    let (user_pda, _user_bump) = Pubkey::find_program_address(
-      &[b"user".as_ref(), authority_account.key.as_ref()],
-      program_id 
-    );
+    let (config_pda, config_bump) = find_config_address(program_id);
 
     // Guard: Ensure Account Key Received Matches PDA
-    if user_pda != *user_account.key {
-      return Err(TradeError::InvalidUserAccount.into())
+    check_pda(config_account, Ok(config_pda), TradeError::InvalidConfigAccount)?;
+
+    // Calculate Lamports needed for PDA
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(Config::LEN);
+
+    // Create Config Account (invoke signed as using PDA)
+    msg!("Creating Config account");
+    invoke_signed(
+      &system_instruction::create_account(
+        admin_account.key,
+        config_account.key,
+        rent_lamports,
+        Config::LEN.try_into().unwrap(),
+        program_id
+      ),
+      &[admin_account.clone(), config_account.clone(), system_program.clone()],
+      &[&[CONFIG_SEED, &[config_bump]]]
+    )?;
+
+    // Initialize Config Account State
+    let mut config_account_state = Config::try_from_slice(&config_account.data.borrow())?;
+    config_account_state.admin = *admin_account.key;
+    config_account_state.pause_flags = 0;
+    config_account_state.bump = config_bump;
+    config_account_state.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::ConfigInitialized { admin: *admin_account.key }.emit()?;
+
+    // Return Result
+    msg!("Config Account Created");
+    Ok(())
+  }
+
+
+  // Set Pause Flags
+  fn set_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pause: Pause,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Admin
+    let mut config_account_state = Self::verify_admin(program_id, admin_account, config_account, account_info_iter.as_slice())?;
+    check_writable(config_account)?;
+
+    // Guard: Known Flags only
+    if pause.flags & !(PAUSE_MAKE | PAUSE_TAKE | PAUSE_CLAIM | PAUSE_WITHDRAW) != 0 {
+      return Err(TradeError::InvalidInstruction.into());
     }
 
-    // Ensure enough to keep account open
-    if **user_account.try_borrow_lamports()? < trade_lamports {
-      return Err(TradeError::NotEnoughLamports.into());
+    // Update Config Account State
+    config_account_state.pause_flags = pause.flags;
+    config_account_state.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::PauseSet { pause_flags: pause.flags }.emit()?;
+
+    // Return Result
+    msg!("Pause Flags Set: {:?}", pause.flags);
+    Ok(())
+  }
+
+
+  // List Market
+  fn list_market(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    market: ListMarket,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+    let market_account = next_account_info(account_info_iter)?; // Market Address (only Public Key Exists at this point)
+    let observations_account = next_account_info(account_info_iter)?; // Observations Address (only Public Key Exists at this point)
+    let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
+    let payer_account = next_account_info(account_info_iter)?; // Holder = Payer Wallet (Admin Wallet or any Signer)
+
+    // Guard: Admin
+    Self::verify_admin(program_id, admin_account, config_account, account_info_iter.as_slice())?;
+
+    // Guard: Accounts
+    check_writable(market_account)?;
+    check_writable(observations_account)?;
+    check_system_program(system_program)?;
+    check_signer(payer_account)?;
+    check_writable(payer_account)?;
+
+    // Guard: Duration bounds and calendar (the TWAP window fits inside the shortest Trade)
+    if market.min_duration == 0 || market.min_duration > market.max_duration
+      || (market.expiry_interval != 0 && market.expiry_offset >= market.expiry_interval)
+      || market.twap_window > market.min_duration {
+      return Err(TradeError::InvalidMarketDurations.into());
     }
 
-    // Debit from_account and credit to_account
-    **user_account.try_borrow_mut_lamports()? -= trade_lamports;
-    **trade_account.try_borrow_mut_lamports()? += trade_lamports;
+    // Guard: Symbol fits a PDA seed
+    if market.symbol.len() > MAX_SEED_LEN {
+      msg!("Symbol longer than {:?} bytes", MAX_SEED_LEN);
+      return Err(TradeError::SeedTooLong.into());
+    }
 
-    // Return
-    msg!("Open Order Created");
+    // Generate Program Derived Addresses (PDA)
+    let (market_pda, market_bump) = find_market_address(program_id, &market.symbol);
+    let (observations_pda, observations_bump) = find_observations_address(program_id, &market.symbol);
+
+    // Guard: Ensure Account Keys Received Match PDAs
+    check_pda(market_account, Ok(market_pda), TradeError::InvalidMarketAccount)?;
+    check_pda(observations_account, Ok(observations_pda), TradeError::InvalidObservationsAccount)?;
+
+    // Calculate Lamports needed for PDA
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(Market::LEN);
+
+    // Create Market Account (invoke signed as using PDA)
+    msg!("Creating Market account");
+    invoke_signed(
+      &system_instruction::create_account(
+        payer_account.key,
+        market_account.key,
+        rent_lamports,
+        Market::LEN.try_into().unwrap(),
+        program_id
+      ),
+      &[payer_account.clone(), market_account.clone(), system_program.clone()],
+      &[&[MARKET_SEED, market.symbol.as_ref(), &[market_bump]]]
+    )?;
+
+    // Create Observations Account (ring buffer for TWAP settlement)
+    msg!("Creating Observations account");
+    invoke_signed(
+      &system_instruction::create_account(
+        payer_account.key,
+        observations_account.key,
+        rent.minimum_balance(Observations::LEN),
+        Observations::LEN.try_into().unwrap(),
+        program_id
+      ),
+      &[payer_account.clone(), observations_account.clone(), system_program.clone()],
+      &[&[OBSERVATIONS_SEED, market.symbol.as_ref(), &[observations_bump]]]
+    )?;
+    Observations { bump: observations_bump, head: 0, samples: vec![] }
+      .serialize(&mut &mut observations_account.data.borrow_mut()[..])?;
+
+    // Initialize Market Account State
+    // Use Unchecked if working with Strings
+    let mut market_account_state = try_from_slice_unchecked::<Market>(&market_account.data.borrow())?;
+    market_account_state.symbol = market.symbol;
+    market_account_state.halted = false;
+    market_account_state.bump = market_bump;
+    market_account_state.min_duration = market.min_duration;
+    market_account_state.max_duration = market.max_duration;
+    market_account_state.expiry_interval = market.expiry_interval;
+    market_account_state.expiry_offset = market.expiry_offset;
+    market_account_state.tie_outcome = market.tie_outcome;
+    market_account_state.late_outcome = market.late_outcome;
+    market_account_state.claim_window = market.claim_window;
+    market_account_state.dust_recipient = market.dust_recipient;
+    market_account_state.challenge_window = market.challenge_window;
+    market_account_state.twap_window = market.twap_window;
+    market_account_state.max_observation_age = market.max_observation_age;
+    market_account_state.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::MarketListed { market: *market_account.key, symbol: market_account_state.symbol }.emit()?;
+
+    // Return Result
+    msg!("Market Listed");
     Ok(())
   }
 
+
+  // Set Market Halt
+  fn set_market_halt(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    halt: MarketHalt,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+    let market_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Admin
+    Self::verify_admin(program_id, admin_account, config_account, account_info_iter.as_slice())?;
+
+    // Guard: Market Account
+    let mut market_account_state = Self::load_market(program_id, market_account)?;
+    check_writable(market_account)?;
+
+    // Update Market Account State
+    market_account_state.halted = halt.halted;
+    market_account_state.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::MarketHaltSet { market: *market_account.key, halted: halt.halted }.emit()?;
+
+    // Return Result
+    msg!("Market {:?} Halted: {:?}", market_account_state.symbol, halt.halted);
+    Ok(())
+  }
+
+
+  // Propose Admin
+  // Step one of two: new Admin must AcceptAdmin before taking over
+  fn propose_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposal: ProposeAdmin,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Admin
+    let mut config_account_state = Self::verify_admin(program_id, admin_account, config_account, account_info_iter.as_slice())?;
+    check_writable(config_account)?;
+
+    // Update Config Account State
+    config_account_state.pending_admin = proposal.new_admin;
+    config_account_state.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::AdminProposed { pending_admin: proposal.new_admin }.emit()?;
+
+    // Return Result
+    msg!("Admin Proposed: {:?}", proposal.new_admin);
+    Ok(())
+  }
+
+
+  // Accept Admin
+  // Step two of two: Pending Admin (Wallet or Multisig) proves control
+  fn accept_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let pending_admin_account = next_account_info(account_info_iter)?; // Holder = Pending Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Pending Admin
+    let mut config_account_state = Self::load_config(program_id, config_account)?;
+    check_writable(config_account)?;
+    if config_account_state.pending_admin == Pubkey::default()
+      || config_account_state.pending_admin != *pending_admin_account.key {
+      return Err(TradeError::NotPendingAdmin.into());
+    }
+    Self::verify_authority(program_id, pending_admin_account, account_info_iter.as_slice())?;
+
+    // Update Config Account State
+    config_account_state.admin = config_account_state.pending_admin;
+    config_account_state.pending_admin = Pubkey::default();
+    config_account_state.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::AdminAccepted { admin: config_account_state.admin }.emit()?;
+
+    // Return Result
+    msg!("Admin Accepted: {:?}", config_account_state.admin);
+    Ok(())
+  }
+
+
+  // Create Multisig
+  fn create_multisig(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    multisig: CreateMultisig,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?; // Holder = Payer Wallet
+    let multisig_account = next_account_info(account_info_iter)?; // Multisig Address (only Public Key Exists at this point)
+    let system_program = next_account_info(account_info_iter)?; // Holder = Program (System Program)
+
+    // Guard: Accounts
+    check_signer(payer_account)?;
+    check_writable(payer_account)?;
+    check_writable(multisig_account)?;
+    check_system_program(system_program)?;
+
+    // Guard: 1 <= m <= n <= MAX_MULTISIG_SIGNERS with no duplicate Signers
+    let n = multisig.signers.len();
+    if multisig.m == 0 || multisig.m as usize > n || n > MAX_MULTISIG_SIGNERS
+      || (1..n).any(|i| multisig.signers[..i].contains(&multisig.signers[i])) {
+      return Err(TradeError::InvalidMultisig.into());
+    }
+
+    // Generate Program Derived Address (PDA)
+    let (multisig_pda, multisig_bump) = find_multisig_address(program_id, payer_account.key);
+
+    // Guard: Ensure Account Key Received Matches PDA
+    check_pda(multisig_account, Ok(multisig_pda), TradeError::InvalidMultisig)?;
+
+    // Calculate Lamports needed for PDA
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(Multisig::LEN);
+
+    // Create Multisig Account (invoke signed as using PDA)
+    msg!("Creating Multisig account");
+    invoke_signed(
+      &system_instruction::create_account(
+        payer_account.key,
+        multisig_account.key,
+        rent_lamports,
+        Multisig::LEN.try_into().unwrap(),
+        program_id
+      ),
+      &[payer_account.clone(), multisig_account.clone(), system_program.clone()],
+      &[&[MULTISIG_SEED, payer_account.key.as_ref(), &[multisig_bump]]]
+    )?;
+
+    // Initialize Multisig Account State
+    let multisig_state = Multisig {
+      m: multisig.m,
+      bump: multisig_bump,
+      signers: multisig.signers,
+    };
+    multisig_state.serialize(&mut &mut multisig_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::MultisigCreated {
+      multisig: *multisig_account.key,
+      m: multisig_state.m,
+      signers: multisig_state.signers,
+    }.emit()?;
+
+    // Return Result
+    msg!("Multisig Created: {:?} of {:?}", multisig.m, n);
+    Ok(())
+  }
+
+
+  // Cancel Trade
+  // Refunds the Maker stake on an unfilled Open Order (allowed while paused or halted)
+  fn cancel_trade(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let signer_account = next_account_info(account_info_iter)?; // Holder = User (Authority or Session Key)
+    let user_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+    let trade_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Created by Maker
+
+    // Guard: Signer (Authority or Session Key) and User Account
+    Self::verify_trade_signer(program_id, signer_account, user_account, SESSION_SCOPE_CANCEL)?;
+
+    // Get Trade Account (Owner and PDA)
+    let mut trade_account_state = Self::load_trade(program_id, trade_account)?;
+
+    // Guard: Ensure Trade Account Details Match Maker
+    if trade_account_state.maker != *user_account.key {
+      msg!("Maker Details do Not Match");
+      return Err(TradeError::InvalidTradeAccount.into())
+    }
+
+    // Guard: Ensure Trade is an unfilled Open Order
+    if trade_account_state.order_status != OrderStatus::Open {
+      return Err(TradeError::InvalidTradeForCancel.into())
+    }
+
+    // Update Trade Account
+    trade_account_state.order_status.transition(OrderStatus::Cancelled)?;
+    trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
+
+    // Update User Account State
+    let trade_lamports = trade_account_state.contract_size.lamports();
+    let mut user_account_state = User::try_from_slice(&user_account.data.borrow())?;
+    Self::release_stake(&mut user_account_state, trade_lamports, trade_lamports)?;
+    user_account_state.serialize(&mut &mut user_account.data.borrow_mut()[..])?;
+
+    // Refund Maker Stake
+    Self::transfer_lamports(trade_account, user_account, trade_lamports)?;
+
+    // Emit Event
+    TradeEvent::TradeCancelled { trade: *trade_account.key, maker: *user_account.key, refund: trade_lamports }.emit()?;
+
+    // Return Result
+    msg!("Open Order Cancelled");
+    Ok(())
+  }
+
+
+  // Set Resolver
+  fn set_resolver(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    resolver: SetResolver,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?; // Holder = Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+
+    // Guard: Admin
+    let mut config_account_state = Self::verify_admin(program_id, admin_account, config_account, account_info_iter.as_slice())?;
+    check_writable(config_account)?;
+
+    // Update Config Account State
+    config_account_state.resolver = resolver.resolver;
+    config_account_state.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::ResolverSet { resolver: resolver.resolver }.emit()?;
+
+    // Return Result
+    msg!("Resolver Set: {:?}", resolver.resolver);
+    Ok(())
+  }
+
+
+  // Resolve Trade
+  // Admin or Resolver voids a Trade (refunding Stakes) or replaces the Closing Price before payout
+  fn resolve_trade(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    resolve: ResolveTrade,
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let resolver_account = next_account_info(account_info_iter)?; // Holder = Resolver Wallet, or Admin Wallet or Multisig (Multisig Signers follow last)
+    let config_account = next_account_info(account_info_iter)?; // Holder = Program (PDA)
+    let trade_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Created by Maker
+
+    // Guard: Admin or Resolver
+    Self::verify_resolver(program_id, resolver_account, config_account, account_info_iter.as_slice())?;
+
+    // Guard: Reason recorded within bounds
+    if resolve.reason.is_empty() || resolve.reason.len() > MAX_REASON_LEN {
+      return Err(TradeError::InvalidResolution.into())
+    }
+
+    // Get Trade Account (Owner and PDA)
+    let mut trade_account_state = Self::load_trade(program_id, trade_account)?;
+
+    // Guard: InPlay once expired, or Pending within the challenge window
+    let unix_current = Self::unix_now()?;
+    match trade_account_state.order_status {
+      OrderStatus::InPlay => {
+        if unix_current < trade_account_state.unix_end {
+          return Err(TradeError::InvalidTimeForClaim.into())
+        }
+      },
+      OrderStatus::Pending => {
+        if unix_current >= Self::unix_final(&trade_account_state)? {
+          return Err(TradeError::ChallengeWindowClosed.into())
+        }
+      },
+      _ => return Err(TradeError::InvalidTradeForClaim.into()),
+    }
+    trade_account_state.resolution_reason = resolve.reason.clone();
+
+    match resolve.resolution {
+
+      // Void: both Stakes refundable now
+      Resolution::Void => {
+        trade_account_state.order_status.transition(OrderStatus::Voided)?;
+        Self::record_payouts(trade_account, &mut trade_account_state, Outcome::Void)?;
+        trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
+      },
+
+      // Closing Price: recorded as if Claimed (never late), paid by Claim after the challenge window
+      Resolution::ClosingPrice(closing_price) => {
+        if closing_price <= 0 {
+          return Err(TradeError::InvalidResolution.into())
+        }
+        if trade_account_state.order_status == OrderStatus::InPlay {
+          trade_account_state.unix_claimed = unix_current;
+          trade_account_state.late = false;
+          trade_account_state.order_status.transition(OrderStatus::Pending)?;
+        }
+        trade_account_state.closing_price = closing_price;
+        trade_account_state.serialize(&mut &mut trade_account.data.borrow_mut()[..])?;
+      },
+    }
+
+    // Emit Event
+    let voided = resolve.resolution == Resolution::Void;
+    TradeEvent::TradeResolved {
+      trade: *trade_account.key,
+      resolver: *resolver_account.key,
+      voided,
+      closing_price: if voided { 0 } else { trade_account_state.closing_price },
+      reason: resolve.reason,
+    }.emit()?;
+
+    // Return Result
+    msg!("Trade Resolved: {:?}", resolve.resolution);
+    Ok(())
+  }
+
+
+  // Record Observation
+  // Permissionless crank appending the latest Chainlink round to the Market's Observations
+  fn record_observation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Extract Accounts
+    let account_info_iter = &mut accounts.iter();
+    let observations_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Market Observations
+    let market_account = next_account_info(account_info_iter)?; // Holder = Program (PDA) Listed by Admin
+    let chainlink_feed_account = next_account_info(account_info_iter)?; // Chainlink Price Feed Data Account
+    let chainlink_program = next_account_info(account_info_iter)?; // Chainlink Program Account
+
+    // Guard: Accounts
+    check_chainlink(chainlink_feed_account, chainlink_program)?;
+    check_writable(observations_account)?;
+    let market_account_state = Self::load_market(program_id, market_account)?;
+    let mut observations_account_state = Self::load_observations(program_id, observations_account, &market_account_state.symbol)?;
+
+    // Get Chainlink Round
+    let chainlink_round = chainlink::latest_round_data(chainlink_program.clone(), chainlink_feed_account.clone())?;
+    let chainlink_asset_description = chainlink::description(chainlink_program.clone(), chainlink_feed_account.clone())?;
+
+    // Guard: Ensure Asset Matches the Market
+    if chainlink_asset_description != market_account_state.symbol {
+      msg!("Chainlink Symbol Mismatch to Market");
+      return Err(TradeError::ChainlinkMismatch.into())
+    }
+
+    // Guard: Ensure Chainlink Price
+    if chainlink_round.answer <= 0 {
+      msg!("Chainlink Price Received as Zero. Chainlink Data Issue.");
+      return Err(TradeError::ChainlinkDataIssue.into())
+    }
+
+    // Update Observations Account (one sample per round timestamp)
+    let observation = Observation {
+      unix_timestamp: chainlink_round.timestamp,
+      price: chainlink_round.answer,
+      round_id: chainlink_round.round_id,
+    };
+    observations_account_state.record(observation)?;
+    observations_account_state.serialize(&mut &mut observations_account.data.borrow_mut()[..])?;
+
+    // Emit Event
+    TradeEvent::ObservationRecorded {
+      market: *market_account.key,
+      unix_timestamp: observation.unix_timestamp,
+      price: observation.price,
+      round_id: observation.round_id,
+    }.emit()?;
+
+    // Return Result
+    Ok(())
+  }
+
+
+  // Guard: Instruction not Paused (Config Account is the last account)
+  fn check_not_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pause_flag: u8,
+  ) -> ProgramResult {
+    let config_account = accounts.last().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let config_account_state = Self::load_config(program_id, config_account)?;
+    if config_account_state.pause_flags & pause_flag != 0 {
+      msg!("Paused by Admin. Flags: {:?}", config_account_state.pause_flags);
+      return Err(TradeError::Paused.into());
+    }
+    Ok(())
+  }
+
+
+  // Load Config Account after checking Owner and PDA (stored bump)
+  fn load_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+  ) -> Result<Config, ProgramError> {
+    check_owner(config_account, program_id)?;
+    let config_account_state = Config::try_from_slice(&config_account.data.borrow())?;
+    let config_pda = create_config_address_with_bump(program_id, config_account_state.bump);
+    check_pda(config_account, config_pda, TradeError::InvalidConfigAccount)?;
+    Ok(config_account_state)
+  }
+
+
+  // Load User Account after checking Owner, Writable and PDA of the Authority (stored bump)
+  fn load_user(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    user_account: &AccountInfo,
+  ) -> Result<User, ProgramError> {
+    check_owner(user_account, program_id)?;
+    check_writable(user_account)?;
+    let user_account_state = User::try_from_slice(&user_account.data.borrow())?;
+    let user_pda = create_user_address_with_bump(program_id, authority, user_account_state.bump);
+    check_pda(user_account, user_pda, TradeError::InvalidUserAccount)?;
+    Ok(user_account_state)
+  }
+
+
+  // Load Trade Account after checking Owner, Writable, size and the PDA rebuilt from its Slug, Maker Authority and bump
+  fn load_trade(
+    program_id: &Pubkey,
+    trade_account: &AccountInfo,
+  ) -> Result<Trade, ProgramError> {
+    check_owner(trade_account, program_id)?;
+    check_writable(trade_account)?;
+    if trade_account.data_len() != Trade::LEN {
+      return Err(TradeError::InvalidTradeAccount.into());
+    }
+    let trade_account_state = try_from_slice_unchecked::<Trade>(&trade_account.data.borrow())?;
+    let trade_pda = create_trade_address_with_bump(
+      program_id, &trade_account_state.slug, &trade_account_state.maker_authority, trade_account_state.bump,
+    );
+    check_pda(trade_account, trade_pda, TradeError::InvalidTradeAccount)?;
+    Ok(trade_account_state)
+  }
+
+
+  // Verify Signer is the Admin stored on Config
+  fn verify_admin(
+    program_id: &Pubkey,
+    admin_account: &AccountInfo,
+    config_account: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+  ) -> Result<Config, ProgramError> {
+    let config_account_state = Self::load_config(program_id, config_account)?;
+    if config_account_state.admin != *admin_account.key {
+      return Err(TradeError::NotAdmin.into());
+    }
+    Self::verify_authority(program_id, admin_account, signer_accounts)?;
+    Ok(config_account_state)
+  }
+
+
+  // Verify Signer is the Resolver stored on Config, or the Admin
+  fn verify_resolver(
+    program_id: &Pubkey,
+    resolver_account: &AccountInfo,
+    config_account: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+  ) -> ProgramResult {
+    let config_account_state = Self::load_config(program_id, config_account)?;
+    if config_account_state.resolver != Pubkey::default() && config_account_state.resolver == *resolver_account.key {
+      return check_signer(resolver_account);
+    }
+    if config_account_state.admin != *resolver_account.key {
+      return Err(TradeError::NotResolver.into());
+    }
+    Self::verify_authority(program_id, resolver_account, signer_accounts)
+  }
+
+
+  // Verify Authority signed directly, or is a Multisig with m of its Signers in signer_accounts
+  fn verify_authority(
+    program_id: &Pubkey,
+    authority_account: &AccountInfo,
+    signer_accounts: &[AccountInfo],
+  ) -> ProgramResult {
+
+    // Wallet Authority
+    if authority_account.owner != program_id {
+      return check_signer(authority_account);
+    }
+
+    // Multisig Authority: count distinct Signers in the set
+    if authority_account.data_len() != Multisig::LEN {
+      return Err(TradeError::InvalidMultisig.into());
+    }
+    let multisig_state = try_from_slice_unchecked::<Multisig>(&authority_account.data.borrow())?;
+    if multisig_state.m == 0 {
+      return Err(TradeError::InvalidMultisig.into());
+    }
+    let mut matched = vec![false; multisig_state.signers.len()];
+    for signer in signer_accounts.iter().filter(|a| a.is_signer) {
+      if let Some(position) = multisig_state.signers.iter().position(|key| key == signer.key) {
+        matched[position] = true;
+      }
+    }
+    if matched.iter().filter(|m| **m).count() < multisig_state.m as usize {
+      msg!("Multisig requires {:?} Signers", multisig_state.m);
+      return Err(TradeError::NotEnoughMultisigSigners.into());
+    }
+    Ok(())
+  }
+
+
+  // Load Market Account after checking Owner and PDA (stored symbol and bump)
+  fn load_market(
+    program_id: &Pubkey,
+    market_account: &AccountInfo,
+  ) -> Result<Market, ProgramError> {
+    check_owner(market_account, program_id)?;
+    let market_account_state = try_from_slice_unchecked::<Market>(&market_account.data.borrow())?;
+    let market_pda = create_market_address_with_bump(program_id, &market_account_state.symbol, market_account_state.bump);
+    check_pda(market_account, market_pda, TradeError::InvalidMarketAccount)?;
+    Ok(market_account_state)
+  }
+
+
+  // Load Observations Account of a Symbol after checking Owner and PDA (stored bump)
+  fn load_observations(
+    program_id: &Pubkey,
+    observations_account: &AccountInfo,
+    symbol: &str,
+  ) -> Result<Observations, ProgramError> {
+    check_owner(observations_account, program_id)?;
+    if observations_account.data_len() != Observations::LEN {
+      return Err(TradeError::InvalidObservationsAccount.into());
+    }
+    let observations_account_state = try_from_slice_unchecked::<Observations>(&observations_account.data.borrow())?;
+    let observations_pda = create_observations_address_with_bump(program_id, symbol, observations_account_state.bump);
+    check_pda(observations_account, observations_pda, TradeError::InvalidObservationsAccount)?;
+    Ok(observations_account_state)
+  }
+
+
+  // Price as of unix_time from the Market's Observations
+  // None when the Market uses live rounds only or has no Observation at most max_age old
+  fn observed_price(
+    program_id: &Pubkey,
+    observations_account: &AccountInfo,
+    symbol: &str,
+    unix_time: u32,
+    max_age: u32,
+  ) -> Result<Option<i128>, ProgramError> {
+    if max_age == 0 {
+      return Ok(None);
+    }
+    let observations_account_state = Self::load_observations(program_id, observations_account, symbol)?;
+    Ok(observations_account_state.price_at(unix_time, max_age))
+  }
+
+
+  // Guard: Market Listed for Symbol and not Halted
+  // Returns the Market (Duration bounds and calendar)
+  fn check_market_open(
+    program_id: &Pubkey,
+    market_account: &AccountInfo,
+    symbol: &str,
+  ) -> Result<Market, ProgramError> {
+    let market_account_state = Self::load_market(program_id, market_account)?;
+    if market_account_state.symbol != symbol {
+      msg!("Market Symbol Mismatch to Trade");
+      return Err(TradeError::InvalidMarketAccount.into());
+    }
+    if market_account_state.halted {
+      msg!("Market Halted: {:?}", symbol);
+      return Err(TradeError::MarketHalted.into());
+    }
+    Ok(market_account_state)
+  }
+
+
+  // Verify Signer is the User Authority or a live Session Key with the required scope
+  // Returns the User Authority
+  fn verify_trade_signer(
+    program_id: &Pubkey,
+    signer_account: &AccountInfo,
+    user_account: &AccountInfo,
+    scope: u8,
+  ) -> Result<Pubkey, ProgramError> {
+
+    // Guard: Signer
+    check_signer(signer_account)?;
+
+    // Guard: User Account Owner and Writable
+    check_owner(user_account, program_id)?;
+    check_writable(user_account)?;
+
+    // Guard: Ensure Account Key Received Matches PDA of stored Authority (stored bump)
+    let user_account_state = User::try_from_slice(&user_account.data.borrow())?;
+    let user_pda = create_user_address_with_bump(program_id, &user_account_state.authority, user_account_state.bump);
+    check_pda(user_account, user_pda, TradeError::InvalidUserAccount)?;
+
+    // Authority always allowed
+    if *signer_account.key == user_account_state.authority {
+      return Ok(user_account_state.authority);
+    }
+
+    // Guard: Session Key matches, not expired and in scope
+    let clock = Clock::get()?;
+    if *signer_account.key != user_account_state.session_key
+      || clock.unix_timestamp >= i64::from(user_account_state.session_expiry)
+      || user_account_state.session_scope & scope == 0 {
+      msg!("Signer is not the Authority or a valid Session Key");
+      return Err(TradeError::InvalidSessionKey.into());
+    }
+    Ok(user_account_state.authority)
+  }
+
+
+  // Record Collected Trade on User Account Stats
+  // outcome: 1 = Won, -1 = Lost, 0 = Drawn
+  fn record_settlement(
+    user_account_state: &mut User,
+    outcome: i8,
+    payout: u64,
+    trade_lamports: u64,
+  ) -> ProgramResult {
+    let count = match outcome {
+      1 => &mut user_account_state.trades_won,
+      -1 => &mut user_account_state.trades_lost,
+      _ => &mut user_account_state.trades_drawn,
+    };
+    *count = count.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    let pnl = i64::try_from(i128::from(payout) - i128::from(trade_lamports)).map_err(|_| TradeError::MathOverflow)?;
+    user_account_state.realized_pnl = user_account_state.realized_pnl.checked_add(pnl).ok_or(TradeError::MathOverflow)?;
+    Self::release_stake(user_account_state, trade_lamports, payout)?;
+    Ok(())
+  }
+
+
+  // Move a Stake from Free to Locked Balance and open a Position
+  fn lock_stake(user_account_state: &mut User, trade_lamports: u64) -> ProgramResult {
+    user_account_state.free_balance = user_account_state.free_balance.checked_sub(trade_lamports)
+      .ok_or(TradeError::InsufficientFreeBalance)?;
+    user_account_state.locked_balance = user_account_state.locked_balance.checked_add(trade_lamports)
+      .ok_or(TradeError::MathOverflow)?;
+    user_account_state.open_positions = user_account_state.open_positions.checked_add(1).ok_or(TradeError::MathOverflow)?;
+    Ok(())
+  }
+
+
+  // Release a Stake from Locked Balance, credit the payout to Free Balance and close the Position
+  fn release_stake(user_account_state: &mut User, trade_lamports: u64, payout: u64) -> ProgramResult {
+    user_account_state.locked_balance = user_account_state.locked_balance.checked_sub(trade_lamports)
+      .ok_or(TradeError::MathOverflow)?;
+    user_account_state.free_balance = user_account_state.free_balance.checked_add(payout).ok_or(TradeError::MathOverflow)?;
+    user_account_state.open_positions = user_account_state.open_positions.checked_sub(1).ok_or(TradeError::MathOverflow)?;
+    Ok(())
+  }
+
+
+  // Debit a Program owned Account and credit another with checked math
+  // (credit reads after the debit in case both are the same Account)
+  fn transfer_lamports(from_account: &AccountInfo, to_account: &AccountInfo, amount: u64) -> ProgramResult {
+    let from_lamports = from_account.lamports().checked_sub(amount).ok_or(TradeError::NotEnoughLamports)?;
+    **from_account.try_borrow_mut_lamports()? = from_lamports;
+    let to_lamports = to_account.lamports().checked_add(amount).ok_or(TradeError::MathOverflow)?;
+    **to_account.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+  }
+
+
+  // End of the challenge window of a recorded Claim
+  fn unix_final(trade_account_state: &Trade) -> Result<u32, ProgramError> {
+    trade_account_state.unix_claimed.checked_add(trade_account_state.challenge_window)
+      .ok_or_else(|| TradeError::MathOverflow.into())
+  }
+
+
+  // Unix time of the Clock as stored on Accounts
+  fn unix_now() -> Result<u32, ProgramError> {
+    let clock = Clock::get()?;
+    u32::try_from(clock.unix_timestamp).map_err(|_| TradeError::MathOverflow.into())
+  }
+
 } 
@@ -1,17 +1,152 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{pubkey::Pubkey};
+use solana_program::{pubkey::{Pubkey, MAX_SEED_LEN}};
 use std::mem;
+use crate::error::TradeError;
 
 
 // User Account
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct User {
-  pub trades_placed: u32,
+  pub trades_placed: u32, // Made + Taken
+  pub trades_made: u32, // Open Trade
+  pub trades_taken: u32, // Take Trade
+  pub trades_won: u32, // Claim
+  pub trades_lost: u32, // Claim
+  pub trades_drawn: u32, // Claim
+  pub total_volume: u64, // Lamports staked across Made and Taken trades
+  pub realized_pnl: i64, // Lamports paid out less Lamports staked on Claimed trades
+  pub deposited: u64, // Lamports deposited via Deposit Instruction
+  pub free_balance: u64, // Lamports available to Withdraw or Stake
+  pub locked_balance: u64, // Lamports staked on Open and InPlay trades
+  pub open_positions: u32, // Open and InPlay trades (must be 0 to Close)
+  pub authority: Pubkey, // Users Main Wallet
+  pub session_key: Pubkey, // Delegate allowed to sign within session_scope
+  pub session_expiry: u32, // Unix time Session Key stops being accepted
+  pub session_scope: u8, // Bit flags of SESSION_SCOPE_*
+  pub bump: u8, // User PDA bump (checked with create_program_address)
 }
 
+// Session Key Scopes (Withdraw is never delegated)
+pub const SESSION_SCOPE_MAKE: u8 = 1;
+pub const SESSION_SCOPE_TAKE: u8 = 1 << 1;
+pub const SESSION_SCOPE_CANCEL: u8 = 1 << 2;
+
 // Get LEN of User Account
 impl User {
-  pub const LEN: usize = mem::size_of::<u32>();
+  pub const LEN: usize = mem::size_of::<u32>() * 8 + mem::size_of::<u64>() * 4 + mem::size_of::<i64>() +
+  mem::size_of::<Pubkey>() * 2 + mem::size_of::<u8>() * 2;
+}
+
+// Trade Direction (Maker's view)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+  Long,
+  Short,
+}
+
+impl Direction {
+  pub fn opposite(self) -> Self {
+    match self {
+      Direction::Long => Direction::Short,
+      Direction::Short => Direction::Long,
+    }
+  }
+}
+
+// Trade Duration (from Take, within the Market bounds)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duration {
+  FiveMinutes,
+  OneHour,
+  OneDay,
+  Seconds(u32), // Custom length
+  Expiry(u32), // Absolute Unix time on the Market calendar (Trades share the settlement round)
+}
+
+impl Duration {
+  // Length from Take (None for a fixed Expiry)
+  pub fn seconds(self) -> Option<u32> {
+    match self {
+      Duration::FiveMinutes => Some(5 * 60),
+      Duration::OneHour => Some(60 * 60),
+      Duration::OneDay => Some(24 * 60 * 60),
+      Duration::Seconds(seconds) => Some(seconds),
+      Duration::Expiry(_) => None,
+    }
+  }
+}
+
+// Contract Size (Stake per side)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractSize {
+  Small, // 0.1 SOL
+  Medium, // 1 SOL
+  Large, // 5 SOL
+}
+
+impl ContractSize {
+  pub fn lamports(self) -> u64 {
+    match self {
+      ContractSize::Small => 100_000_000,
+      ContractSize::Medium => 1_000_000_000,
+      ContractSize::Large => 5_000_000_000,
+    }
+  }
+}
+
+// Settlement Outcome, also the Market policy for a tied price or a late Claim
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+  Void, // Both Stakes refunded
+  Maker, // Maker takes the pot
+  Taker, // Taker takes the pot
+}
+
+// Side of a Trade
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Party {
+  Maker,
+  Taker,
+}
+
+// Order Status
+// Uninitialized -> Open (Make)
+// Open -> InPlay (Take), Cancelled (Cancel) or Voided
+// InPlay -> Settled (Claim), Expired (Claim after the allowance), Pending (Claim with a challenge window) or Voided
+// Pending -> Settled or Expired (Claim after the challenge window) or Voided (Resolver)
+// Settled, Cancelled, Expired and Voided are final
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+  Uninitialized,
+  Open,
+  InPlay,
+  Settled,
+  Cancelled,
+  Expired,
+  Voided,
+  Pending, // Closing Price recorded, payout after the challenge window
+}
+
+impl OrderStatus {
+  pub fn can_transition(self, next: OrderStatus) -> bool {
+    use OrderStatus::*;
+    matches!(
+      (self, next),
+      (Uninitialized, Open)
+        | (Open, InPlay) | (Open, Cancelled) | (Open, Voided)
+        | (InPlay, Settled) | (InPlay, Expired) | (InPlay, Pending) | (InPlay, Voided)
+        | (Pending, Settled) | (Pending, Expired) | (Pending, Voided)
+    )
+  }
+
+  // Move to the next Status or fail without changing it
+  pub fn transition(&mut self, next: OrderStatus) -> Result<(), TradeError> {
+    if !self.can_transition(next) {
+      return Err(TradeError::InvalidStatusTransition);
+    }
+    *self = next;
+    Ok(())
+  }
 }
 
 // Trade Account
@@ -23,19 +158,243 @@ pub struct Trade {
   pub bump: u8, // Maker
   pub slug: String, // Maker
   pub symbol: String, // Maker
-  pub contract_size: u8, // Maker
-  pub direction: u8, // Maker
-  pub duration: u8, // Maker
+  pub contract_size: ContractSize, // Maker
+  pub direction: Direction, // Maker
+  pub duration: Duration, // Maker
   pub unix_created: u32, // Maker
   pub unix_start: u32, // Taker
-  pub unix_end: u32, // Taker (start + duration)
+  pub unix_end: u32, // Taker (start + duration, or the Expiry)
   pub benchmark_price: i128, // Taker
   pub closing_price: i128, // Claimer
-  pub order_status: u8, // All Instructions: 0 = Not Initialized, 1 = OpenOrder, 2 = InPlay, 3 = Claimed
+  pub order_status: OrderStatus, // All Instructions
+  pub allowed_taker: Pubkey, // Maker (Pubkey::default() = anyone)
+  pub reference_price: i128, // Maker (0 = no price protection)
+  pub max_deviation_bps: u16, // Maker
+  pub tie_outcome: Outcome, // Market (at Make)
+  pub late_outcome: Outcome, // Market (at Make)
+  pub claim_window: u32, // Market (at Make)
+  pub dust_recipient: Party, // Market (at Make)
+  pub challenge_window: u32, // Market (at Make)
+  pub unix_claimed: u32, // Claimer or Resolver (Closing Price recorded)
+  pub late: bool, // Claimer (claimed claim_window or more after unix_end)
+  pub twap_window: u32, // Market (at Make)
+  pub max_observation_age: u32, // Market (at Make)
+  pub settled_outcome: Outcome, // Settlement (Void when voided)
+  pub maker_payout: u64, // Settlement, held on the Trade until the Maker collects
+  pub taker_payout: u64, // Settlement, held on the Trade until the Taker collects
+  pub maker_collected: bool, // Maker User Withdraw
+  pub taker_collected: bool, // Taker User Withdraw
+  pub resolution_reason: String, // Resolver (latest void or correction, up to MAX_REASON_LEN bytes)
+  pub maker_authority: Pubkey, // Maker (Trade PDA seed)
 }
 
 // Get LEN of Trade Account
 impl Trade {
-  pub const LEN: usize = mem::size_of::<Pubkey>() * 3 + mem::size_of::<u32>() * 2 + mem::size_of::<i128>() * 2 + 
-  mem::size_of::<u8>() * 5 + mem::size_of::<String>() * 2 + 8; // Add 4 Bytes per String
+  pub const LEN: usize = mem::size_of::<Pubkey>() * 5 + mem::size_of::<u32>() * 8 + mem::size_of::<i128>() * 3 + 
+  mem::size_of::<u64>() * 2 + mem::size_of::<u16>() + mem::size_of::<u8>() * 12 + mem::size_of::<u32>() +
+  MAX_SEED_LEN * 2 + MAX_REASON_LEN + 12; // Bump + Late + 2 Collected + 8 enum tags + Duration u32, Slug and Symbol are PDA Seeds, Add 4 Bytes per String
+
+  // Outcome of the recorded Closing Price (a tie or a late Claim follows the Market policy fixed at Make)
+  pub fn outcome(&self) -> Outcome {
+    if self.late {
+      self.late_outcome
+    } else if self.closing_price == self.benchmark_price {
+      self.tie_outcome
+    } else if (self.direction == Direction::Long) == (self.closing_price > self.benchmark_price) {
+      Outcome::Maker
+    } else {
+      Outcome::Taker
+    }
+  }
+
+  // Sides of a User Account with a Payout still to collect (Settled, Expired or Voided)
+  pub fn uncollected(&self, user: &Pubkey) -> Vec<Party> {
+    if !matches!(self.order_status, OrderStatus::Settled | OrderStatus::Expired | OrderStatus::Voided) {
+      return vec![];
+    }
+    let mut sides = vec![];
+    if self.maker == *user && !self.maker_collected {
+      sides.push(Party::Maker);
+    }
+    if self.taker == *user && !self.taker_collected {
+      sides.push(Party::Taker);
+    }
+    sides
+  }
+
+  // Benchmark within max_deviation_bps of the Maker's Reference Price (always true without one)
+  pub fn within_reference(&self, benchmark_price: i128) -> Result<bool, TradeError> {
+    if self.reference_price == 0 {
+      return Ok(true);
+    }
+    let deviation = benchmark_price.checked_sub(self.reference_price)
+      .and_then(i128::checked_abs)
+      .and_then(|deviation| deviation.checked_mul(10_000))
+      .ok_or(TradeError::MathOverflow)?;
+    let allowed = self.reference_price.checked_mul(self.max_deviation_bps as i128).ok_or(TradeError::MathOverflow)?;
+    Ok(deviation <= allowed)
+  }
+}
+
+
+// Config Account (single PDA for the Program)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Config {
+  pub admin: Pubkey, // Admin Authority (Wallet or Multisig Account)
+  pub pause_flags: u8, // Bit flags of PAUSE_*
+  pub bump: u8,
+  pub pending_admin: Pubkey, // Proposed Admin awaiting AcceptAdmin (default = none)
+  pub resolver: Pubkey, // May void or correct Claims alongside the Admin (default = none)
+}
+
+// Pause Flags (Cancel and Deposit are never paused)
+pub const PAUSE_MAKE: u8 = 1;
+pub const PAUSE_TAKE: u8 = 1 << 1;
+pub const PAUSE_CLAIM: u8 = 1 << 2;
+pub const PAUSE_WITHDRAW: u8 = 1 << 3;
+
+// Get LEN of Config Account
+impl Config {
+  pub const LEN: usize = mem::size_of::<Pubkey>() * 3 + mem::size_of::<u8>() * 2;
+}
+
+// Max bytes in a Resolution reason
+pub const MAX_REASON_LEN: usize = 64;
+
+// Market Account (one PDA per listed symbol)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Market {
+  pub symbol: String, // Chainlink Feed Description
+  pub halted: bool, // No new Make or Take while halted
+  pub bump: u8,
+  pub min_duration: u32, // Shortest Trade in seconds
+  pub max_duration: u32, // Longest Trade in seconds
+  pub expiry_interval: u32, // Calendar Expiries every interval seconds (0 = no calendar)
+  pub expiry_offset: u32, // Calendar Expiries at offset past each interval (e.g. 16:00 UTC daily)
+  pub tie_outcome: Outcome, // Closing Price equals the Benchmark
+  pub late_outcome: Outcome, // Claimed claim_window or more after unix_end
+  pub claim_window: u32, // Seconds after unix_end before a Claim is late (0 = never late)
+  pub dust_recipient: Party, // Receives lamports above the two Stakes
+  pub challenge_window: u32, // Seconds a Resolver may void or correct a Claim before payout (0 = pay at Claim)
+  pub twap_window: u32, // Settle on the average Observation over this many seconds before unix_end (0 = single round)
+  pub max_observation_age: u32, // Closing Price from an Observation at most this old (0 = live rounds only)
+}
+
+// Get LEN of Market Account
+impl Market {
+  pub const LEN: usize = MAX_SEED_LEN + mem::size_of::<u8>() * 5 + mem::size_of::<u32>() * 8 + 4; // Symbol is a PDA Seed, Add 4 Bytes per String
+
+  // Settlement time for a Trade running from unix_now
+  // Custom lengths must lie within the bounds, Expiries on the calendar with at least min_duration left
+  pub fn unix_end(&self, duration: Duration, unix_now: u32) -> Result<u32, TradeError> {
+    let unix_end = match duration {
+      Duration::Expiry(unix_end) => {
+        if self.expiry_interval == 0 || unix_end % self.expiry_interval != self.expiry_offset {
+          return Err(TradeError::ExpiryNotOnCalendar);
+        }
+        unix_end
+      },
+      _ => unix_now.checked_add(duration.seconds().unwrap_or_default()).ok_or(TradeError::MathOverflow)?,
+    };
+    let length = unix_end.checked_sub(unix_now).ok_or(TradeError::DurationOutOfBounds)?;
+    if length < self.min_duration || length > self.max_duration {
+      return Err(TradeError::DurationOutOfBounds);
+    }
+    Ok(unix_end)
+  }
+}
+
+// Price Observation recorded by the crank
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Observation {
+  pub unix_timestamp: u32, // Chainlink round timestamp
+  pub price: i128,
+  pub round_id: u32, // Chainlink round
+}
+
+// Observations Account (ring buffer of Chainlink prices, one PDA per listed Market)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Observations {
+  pub bump: u8,
+  pub head: u16, // Slot overwritten next once full
+  pub samples: Vec<Observation>, // Grows to MAX_OBSERVATIONS, then oldest first from head
+}
+
+// Max Observations kept per Market
+pub const MAX_OBSERVATIONS: usize = 256;
+
+// Max seconds between a Trade's end and the Round a batch settles it on
+pub const MAX_ROUND_DELAY: u32 = 60;
+
+// Get LEN of Observations Account
+impl Observations {
+  pub const LEN: usize = mem::size_of::<u8>() + mem::size_of::<u16>() +
+  (mem::size_of::<u32>() * 2 + mem::size_of::<i128>()) * MAX_OBSERVATIONS + 4; // Add 4 Bytes per Vec
+
+  // Most recent Observation
+  pub fn latest(&self) -> Option<&Observation> {
+    match self.head as usize {
+      0 => self.samples.last(),
+      head => self.samples.get(head - 1),
+    }
+  }
+
+  // Observations oldest first (for charting)
+  pub fn history(&self) -> Vec<Observation> {
+    let (newest, oldest) = self.samples.split_at(self.head as usize);
+    oldest.iter().chain(newest).copied().collect()
+  }
+
+  // Price as of unix_time: the last Observation at or before it, if at most max_age old
+  pub fn price_at(&self, unix_time: u32, max_age: u32) -> Option<i128> {
+    self.samples.iter()
+      .filter(|sample| sample.unix_timestamp <= unix_time)
+      .max_by_key(|sample| sample.unix_timestamp)
+      .filter(|sample| unix_time - sample.unix_timestamp <= max_age)
+      .map(|sample| sample.price)
+  }
+
+  // Append each round once in time order, overwriting the oldest once full
+  pub fn record(&mut self, observation: Observation) -> Result<(), TradeError> {
+    if self.latest().is_some_and(|latest| {
+      observation.unix_timestamp <= latest.unix_timestamp || observation.round_id == latest.round_id
+    }) {
+      return Err(TradeError::StaleObservation);
+    }
+    if self.samples.len() < MAX_OBSERVATIONS {
+      self.samples.push(observation);
+    } else {
+      self.samples[self.head as usize] = observation;
+      self.head = ((self.head as usize + 1) % MAX_OBSERVATIONS) as u16;
+    }
+    Ok(())
+  }
+
+  // Average price of the Observations from unix_from to unix_to (inclusive)
+  pub fn twap(&self, unix_from: u32, unix_to: u32) -> Result<i128, TradeError> {
+    let (sum, count) = self.samples.iter()
+      .filter(|sample| (unix_from..=unix_to).contains(&sample.unix_timestamp))
+      .try_fold((0i128, 0i128), |(sum, count), sample| Some((sum.checked_add(sample.price)?, count + 1)))
+      .ok_or(TradeError::MathOverflow)?;
+    if count == 0 {
+      return Err(TradeError::NoObservations);
+    }
+    Ok(sum / count)
+  }
+}
+
+// Multisig Account (m of n Signers able to act as Admin)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Multisig {
+  pub m: u8, // Signatures required
+  pub bump: u8,
+  pub signers: Vec<Pubkey>, // n Signers
+}
+
+// Max Signers on a Multisig
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+// Get LEN of Multisig Account
+impl Multisig {
+  pub const LEN: usize = mem::size_of::<u8>() * 2 + mem::size_of::<Pubkey>() * MAX_MULTISIG_SIGNERS + 4; // Add 4 Bytes per Vec
 }
@@ -0,0 +1,130 @@
+use solana_program::{
+  account_info::AccountInfo,
+  entrypoint::ProgramResult,
+  msg,
+  program_error::ProgramError,
+  pubkey::{Pubkey, PubkeyError},
+  system_program,
+};
+use crate::error::TradeError;
+
+
+// ACCOUNT VALIDATION
+// Every Instruction runs these against each Account before reading or moving anything
+
+
+// Account signed the Transaction
+pub fn check_signer(account: &AccountInfo) -> ProgramResult {
+  if !account.is_signer {
+    msg!("Missing signature: {:?}", account.key);
+    return Err(ProgramError::MissingRequiredSignature);
+  }
+  Ok(())
+}
+
+// Account passed as writable (lamports or data change)
+pub fn check_writable(account: &AccountInfo) -> ProgramResult {
+  if !account.is_writable {
+    msg!("Account not writable: {:?}", account.key);
+    return Err(TradeError::AccountNotWritable.into());
+  }
+  Ok(())
+}
+
+// Account held by the expected Program
+pub fn check_owner(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+  if account.owner != owner {
+    msg!("Account {:?} not owned by {:?}", account.key, owner);
+    return Err(ProgramError::IncorrectProgramId);
+  }
+  Ok(())
+}
+
+// Account is the expected Program
+pub fn check_program(account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+  if account.key != program_id {
+    msg!("Expected Program {:?}, received {:?}", program_id, account.key);
+    return Err(ProgramError::IncorrectProgramId);
+  }
+  Ok(())
+}
+
+// System Program (Create Account and Transfer)
+pub fn check_system_program(account: &AccountInfo) -> ProgramResult {
+  check_program(account, &system_program::id())
+}
+
+// Chainlink Store Program (pinned) and a Feed it owns
+pub fn check_chainlink(feed_account: &AccountInfo, program_account: &AccountInfo) -> ProgramResult {
+  check_program(program_account, &chainlink_solana::ID)?;
+  check_owner(feed_account, &chainlink_solana::ID)
+}
+
+// Account key matches the expected PDA (from find_* or create_*_with_bump)
+pub fn check_pda(account: &AccountInfo, expected: Result<Pubkey, PubkeyError>, error: TradeError) -> ProgramResult {
+  if expected != Ok(*account.key) {
+    msg!("Account {:?} is not the expected PDA", account.key);
+    return Err(error.into());
+  }
+  Ok(())
+}
+
+
+// TEST MODULE
+#[cfg(test)]
+mod test {
+  use super::*;
+  use solana_program::clock::Epoch;
+
+  fn with_account<F: FnOnce(&AccountInfo)>(key: Pubkey, owner: Pubkey, is_signer: bool, is_writable: bool, f: F) {
+    let mut lamports = 0;
+    let mut data = vec![];
+    let account = AccountInfo::new(&key, is_signer, is_writable, &mut lamports, &mut data, &owner, false, Epoch::default());
+    f(&account);
+  }
+
+  #[test]
+  fn test_checks_reject() {
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+
+    with_account(key, program_id, false, false, |account| {
+      assert_eq!(check_signer(account), Err(ProgramError::MissingRequiredSignature));
+      assert_eq!(check_writable(account), Err(TradeError::AccountNotWritable.into()));
+      assert_eq!(check_owner(account, &Pubkey::new_unique()), Err(ProgramError::IncorrectProgramId));
+      assert_eq!(check_program(account, &program_id), Err(ProgramError::IncorrectProgramId));
+      assert_eq!(check_system_program(account), Err(ProgramError::IncorrectProgramId));
+      assert_eq!(check_pda(account, Ok(Pubkey::new_unique()), TradeError::InvalidUserAccount), Err(TradeError::InvalidUserAccount.into()));
+      assert_eq!(
+        check_pda(account, Err(PubkeyError::InvalidSeeds), TradeError::InvalidTradeAccount),
+        Err(TradeError::InvalidTradeAccount.into())
+      );
+    });
+
+    // Pinned Chainlink Program, Feed owned by it
+    with_account(chainlink_solana::ID, Pubkey::default(), false, false, |program| {
+      with_account(key, program_id, false, false, |feed| {
+        assert_eq!(check_chainlink(feed, program), Err(ProgramError::IncorrectProgramId));
+        assert_eq!(check_chainlink(program, feed), Err(ProgramError::IncorrectProgramId));
+      });
+    });
+  }
+
+  #[test]
+  fn test_checks_accept() {
+    let program_id = Pubkey::new_unique();
+
+    with_account(program_id, program_id, true, true, |account| {
+      assert_eq!(check_signer(account), Ok(()));
+      assert_eq!(check_writable(account), Ok(()));
+      assert_eq!(check_owner(account, &program_id), Ok(()));
+      assert_eq!(check_program(account, &program_id), Ok(()));
+      assert_eq!(check_pda(account, Ok(program_id), TradeError::InvalidUserAccount), Ok(()));
+    });
+    with_account(chainlink_solana::ID, Pubkey::default(), false, false, |program| {
+      with_account(Pubkey::new_unique(), chainlink_solana::ID, false, false, |feed| {
+        assert_eq!(check_chainlink(feed, program), Ok(()));
+      });
+    });
+  }
+}